@@ -0,0 +1,784 @@
+use anyhow::{anyhow, bail, Result};
+use packet::{unframe, ReceiveTime};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+use timing::GpsTimestamp;
+
+/// Start of every capture file, followed by the format version.
+pub const MAGIC: &[u8; 7] = b"LORDCAP";
+pub const VERSION: u8 = 2;
+
+const DATA: u8 = 0x01;
+const MARKER: u8 = 0x02;
+
+/// Entry of a capture file. Concatenating the `Data` records of every file
+/// of a recording gives back the bytes read from the device.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Record {
+    Data(Data),
+    Marker(Marker),
+}
+
+/// Bytes from a single read of the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Data {
+    /// Monotonic time since the first bytes of the recording.
+    pub elapsed: Duration,
+    pub bytes: Vec<u8>,
+}
+
+/// Host time at which the bytes following the marker were read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub system: SystemTime,
+    /// Monotonic time since the first bytes of the recording.
+    pub elapsed: Duration,
+}
+
+impl Marker {
+    fn to_bytes(self) -> [u8; 17] {
+        let system = self
+            .system
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+
+        let mut bytes = [0u8; 17];
+        bytes[0] = MARKER;
+        bytes[1..9].copy_from_slice(&system.to_be_bytes());
+        bytes[9..].copy_from_slice(&(self.elapsed.as_nanos() as u64).to_be_bytes());
+        bytes
+    }
+}
+
+/// Path of the `index`th file of a rotated recording, `capture.3.bin` for
+/// the fourth file of `capture.bin`.
+pub fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, index),
+    };
+
+    path.with_file_name(name)
+}
+
+/// Configuration of a raw byte recording, started with `start`.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    max_duration: Option<Duration>,
+    marker_interval: Duration,
+}
+
+impl Recorder {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            max_bytes: None,
+            max_duration: None,
+            marker_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Starts a new file once the current one holds at least `bytes`.
+    pub fn with_max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Starts a new file once the current one spans at least `duration`.
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Minimum time between host timestamp markers, 1 s by default. Every
+    /// file starts with a marker.
+    pub fn with_marker_interval(mut self, interval: Duration) -> Self {
+        self.marker_interval = interval;
+        self
+    }
+
+    /// Creates the first file and starts the thread writing to it.
+    pub fn start(self) -> Result<Recording> {
+        let file = CaptureFile::create(&self.path)?;
+        let (sender, receiver) = channel();
+
+        let writer = std::thread::spawn(move || {
+            Writer {
+                recorder: self,
+                file,
+                index: 0,
+                base: None,
+                last_marker: None,
+            }
+            .run(receiver)
+        });
+
+        Ok(Recording {
+            sender: Some(sender),
+            writer: Some(writer),
+        })
+    }
+}
+
+/// Running recording. Bytes are queued without bound and written by a
+/// separate thread, so slow disks or packet handlers never cause any to be
+/// dropped.
+pub struct Recording {
+    sender: Option<Sender<(ReceiveTime, Vec<u8>)>>,
+    writer: Option<JoinHandle<Result<()>>>,
+}
+
+impl Recording {
+    /// Queues bytes read from the device at `received`. Fails once the
+    /// writer has stopped, `finish` returns the reason.
+    pub fn record(&self, bytes: &[u8], received: ReceiveTime) -> Result<()> {
+        self.sender
+            .as_ref()
+            .and_then(|sender| sender.send((received, bytes.to_vec())).ok())
+            .ok_or_else(|| anyhow!("capture writer has stopped"))
+    }
+
+    /// Writes out every queued byte and closes the file.
+    pub fn finish(mut self) -> Result<()> {
+        self.stop()
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.sender.take();
+
+        match self.writer.take() {
+            Some(writer) => writer
+                .join()
+                .map_err(|_| anyhow!("capture writer panicked"))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            eprintln!("{:?}", e);
+        }
+    }
+}
+
+struct CaptureFile {
+    writer: BufWriter<File>,
+    written: u64,
+    opened: Option<Instant>,
+}
+
+impl CaptureFile {
+    fn create(path: &Path) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        Ok(Self {
+            writer,
+            written: MAGIC.len() as u64 + 1,
+            opened: None,
+        })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+struct Writer {
+    recorder: Recorder,
+    file: CaptureFile,
+    index: usize,
+    base: Option<Instant>,
+    last_marker: Option<Instant>,
+}
+
+impl Writer {
+    fn run(mut self, receiver: Receiver<(ReceiveTime, Vec<u8>)>) -> Result<()> {
+        while let Ok((received, bytes)) = receiver.recv() {
+            self.write(received, &bytes)?;
+
+            for (received, bytes) in receiver.try_iter() {
+                self.write(received, &bytes)?;
+            }
+
+            self.file.writer.flush()?;
+        }
+
+        self.file.writer.flush()?;
+        Ok(())
+    }
+
+    fn write(&mut self, received: ReceiveTime, bytes: &[u8]) -> Result<()> {
+        let base = *self.base.get_or_insert(received.monotonic);
+        let opened = *self.file.opened.get_or_insert(received.monotonic);
+
+        let full = self
+            .recorder
+            .max_bytes
+            .is_some_and(|max| self.file.written >= max);
+        let expired = self
+            .recorder
+            .max_duration
+            .is_some_and(|max| received.monotonic.saturating_duration_since(opened) >= max);
+
+        if full || expired {
+            self.file.writer.flush()?;
+            self.index += 1;
+            self.file = CaptureFile::create(&rotated_path(&self.recorder.path, self.index))?;
+            self.file.opened = Some(received.monotonic);
+            self.last_marker = None;
+        }
+
+        let elapsed = received.monotonic.saturating_duration_since(base);
+        let marker_due = self.last_marker.is_none_or(|last| {
+            received.monotonic.saturating_duration_since(last) >= self.recorder.marker_interval
+        });
+
+        if marker_due {
+            let marker = Marker {
+                system: received.system,
+                elapsed,
+            };
+
+            self.file.write(&marker.to_bytes())?;
+            self.last_marker = Some(received.monotonic);
+        }
+
+        self.file.write(&[DATA])?;
+        self.file
+            .write(&(elapsed.as_nanos() as u64).to_be_bytes())?;
+        self.file.write(&(bytes.len() as u32).to_be_bytes())?;
+        self.file.write(bytes)
+    }
+}
+
+/// Reads back the records of a capture file.
+pub struct CaptureReader<R: Read> {
+    reader: R,
+}
+
+impl CaptureReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+
+        if &header[..7] != MAGIC {
+            bail!("not a capture file");
+        }
+
+        if header[7] != VERSION {
+            bail!("unsupported capture version {}", header[7]);
+        }
+
+        Ok(Self { reader })
+    }
+
+    fn read_record(&mut self, tag: u8) -> Result<Record> {
+        match tag {
+            DATA => {
+                let mut header = [0u8; 12];
+                self.reader.read_exact(&mut header)?;
+
+                let elapsed = u64::from_be_bytes(header[..8].try_into()?);
+                let length = u32::from_be_bytes(header[8..].try_into()?);
+
+                let mut bytes = vec![0u8; length as usize];
+                self.reader.read_exact(&mut bytes)?;
+
+                Ok(Record::Data(Data {
+                    elapsed: Duration::from_nanos(elapsed),
+                    bytes,
+                }))
+            }
+            MARKER => {
+                let mut times = [0u8; 16];
+                self.reader.read_exact(&mut times)?;
+
+                let system = u64::from_be_bytes(times[..8].try_into()?);
+                let elapsed = u64::from_be_bytes(times[8..].try_into()?);
+
+                Ok(Record::Marker(Marker {
+                    system: SystemTime::UNIX_EPOCH + Duration::from_nanos(system),
+                    elapsed: Duration::from_nanos(elapsed),
+                }))
+            }
+            tag => bail!("unknown capture record {:#04x}", tag),
+        }
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut tag = [0u8; 1];
+
+        match self.reader.read(&mut tag) {
+            Ok(0) => None,
+            Ok(_) => Some(self.read_record(tag[0])),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+/// How a `Replay` spaces out the recorded bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pacing {
+    /// As fast as the consumer reads.
+    Fast,
+    /// Following the host time at which each chunk of bytes was read.
+    Host,
+    /// Following the device GPS time of the recorded packets.
+    Gps,
+}
+
+/// Plays back a (possibly rotated) capture as a byte stream, to be handed to
+/// `LordParser::from_reader` or read directly.
+pub struct Replay {
+    path: PathBuf,
+    index: usize,
+    reader: Option<CaptureReader<BufReader<File>>>,
+    data: Vec<u8>,
+    offset: usize,
+    pacing: Pacing,
+    speed: f64,
+    position: Duration,
+    host_base: Option<(Instant, Duration)>,
+    gps_base: Option<(Instant, f64)>,
+    unframed: Vec<u8>,
+}
+
+impl Replay {
+    /// Opens the first file of a recording, the rotated files following it
+    /// are picked up in order.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        Ok(Self {
+            reader: Some(CaptureReader::open(&path)?),
+            path,
+            index: 0,
+            data: Vec::new(),
+            offset: 0,
+            pacing: Pacing::Fast,
+            speed: 1.0,
+            position: Duration::ZERO,
+            host_base: None,
+            gps_base: None,
+            unframed: Vec::new(),
+        })
+    }
+
+    pub fn with_pacing(mut self, pacing: Pacing) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// Playback speed relative to the recording, 2.0 plays twice as fast.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Recording time (since its first bytes) of the latest record read.
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// Continues playback from the first chunk of bytes read at or after
+    /// `position`, which may start partway through a packet. Pacing restarts
+    /// from there. Fails when `position` is past the end of the recording,
+    /// playback is then at its end.
+    pub fn seek(&mut self, position: Duration) -> Result<()> {
+        self.index = 0;
+        self.reader = Some(CaptureReader::open(&self.path)?);
+        self.data.clear();
+        self.offset = 0;
+        self.position = Duration::ZERO;
+        self.host_base = None;
+        self.gps_base = None;
+        self.unframed.clear();
+
+        while let Some(record) = self.next_record()? {
+            match record {
+                Record::Marker(marker) => self.position = marker.elapsed,
+                Record::Data(data) if data.elapsed >= position => {
+                    self.pace_host(data.elapsed);
+                    self.pace_gps(&data.bytes);
+                    self.position = data.elapsed;
+                    self.data = data.bytes;
+                    return Ok(());
+                }
+                Record::Data(data) => self.position = data.elapsed,
+            }
+        }
+
+        bail!(
+            "cannot seek to {:?}, the recording ends at {:?}",
+            position,
+            self.position
+        )
+    }
+
+    fn next_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            let reader = match &mut self.reader {
+                Some(reader) => reader,
+                None => return Ok(None),
+            };
+
+            match reader.next() {
+                Some(record) => return record.map(Some),
+                None => {
+                    self.index += 1;
+                    let path = rotated_path(&self.path, self.index);
+
+                    self.reader = if path.exists() {
+                        Some(CaptureReader::open(path)?)
+                    } else {
+                        None
+                    };
+                }
+            }
+        }
+    }
+
+    fn next_data(&mut self) -> Result<Option<Vec<u8>>> {
+        while let Some(record) = self.next_record()? {
+            match record {
+                Record::Marker(marker) => self.position = marker.elapsed,
+                Record::Data(data) => {
+                    self.pace_host(data.elapsed);
+                    self.pace_gps(&data.bytes);
+                    self.position = data.elapsed;
+                    return Ok(Some(data.bytes));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn pace_host(&mut self, elapsed: Duration) {
+        if self.pacing != Pacing::Host {
+            return;
+        }
+
+        let (start, base) = *self.host_base.get_or_insert((Instant::now(), elapsed));
+        self.sleep_until(start, elapsed.saturating_sub(base).as_secs_f64());
+    }
+
+    fn pace_gps(&mut self, data: &[u8]) {
+        if self.pacing != Pacing::Gps {
+            return;
+        }
+
+        self.unframed.extend_from_slice(data);
+
+        let time = unframe(&mut self.unframed)
+            .iter()
+            .filter_map(|packet| data::Packet::new(packet).ok())
+            .find_map(|packet| GpsTimestamp::from_packet(&packet));
+
+        if let Some(time) = time {
+            let (start, base) = *self
+                .gps_base
+                .get_or_insert((Instant::now(), time.seconds()));
+            self.sleep_until(start, time.seconds() - base);
+        }
+    }
+
+    fn sleep_until(&self, start: Instant, recorded: f64) {
+        if recorded <= 0.0 || self.speed <= 0.0 {
+            return;
+        }
+
+        let target = start + Duration::from_secs_f64(recorded / self.speed);
+        let now = Instant::now();
+
+        if target > now {
+            std::thread::sleep(target - now);
+        }
+    }
+}
+
+impl Read for Replay {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.offset >= self.data.len() {
+            match self.next_data().map_err(std::io::Error::other)? {
+                Some(data) => {
+                    self.data = data;
+                    self.offset = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+
+        let count = buf.len().min(self.data.len() - self.offset);
+        buf[..count].copy_from_slice(&self.data[self.offset..self.offset + count]);
+        self.offset += count;
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use packet::{PacketComponent, RawPacket};
+
+    fn received(base: ReceiveTime, millis: u64) -> ReceiveTime {
+        ReceiveTime {
+            monotonic: base.monotonic + Duration::from_millis(millis),
+            system: base.system + Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn record_and_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        let base = ReceiveTime::now();
+
+        let recording = Recorder::new(&path)
+            .with_max_bytes(64)
+            .with_marker_interval(Duration::from_millis(100))
+            .start()
+            .unwrap();
+
+        let mut sent = Vec::new();
+        for i in 0..20u8 {
+            let bytes = vec![0x75, 0x65, i, i.wrapping_mul(3)];
+            recording
+                .record(&bytes, received(base, i as u64 * 30))
+                .unwrap();
+            sent.extend(bytes);
+        }
+
+        recording.finish().unwrap();
+
+        let mut read = Vec::new();
+        let mut markers = Vec::new();
+        let mut index = 0;
+        while rotated_path(&path, index).exists() {
+            let records = CaptureReader::open(rotated_path(&path, index))
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+
+            assert!(matches!(records[0], Record::Marker(_)));
+
+            for record in records {
+                match record {
+                    Record::Data(data) => read.extend(data.bytes),
+                    Record::Marker(marker) => markers.push(marker),
+                }
+            }
+
+            index += 1;
+        }
+
+        assert!(index > 1);
+        assert_eq!(read, sent);
+        assert_eq!(markers[0].elapsed, Duration::ZERO);
+        assert!(markers
+            .windows(2)
+            .all(|pair| pair[0].elapsed < pair[1].elapsed));
+    }
+
+    #[test]
+    fn rotated_names() {
+        let path = Path::new("/tmp/capture.bin");
+
+        assert_eq!(rotated_path(path, 0), path);
+        assert_eq!(rotated_path(path, 2), Path::new("/tmp/capture.2.bin"));
+        assert_eq!(
+            rotated_path(Path::new("capture"), 1),
+            Path::new("capture.1")
+        );
+    }
+
+    fn gps_packet(tow: f64) -> Vec<u8> {
+        let time = data::shared_data::GpsTimestamp {
+            tow,
+            week: 2200,
+            flags: data::shared_data::GpsTimestampFlags::all(),
+        };
+
+        RawPacket::new(0x80, vec![time.to_raw_field().unwrap()])
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+    }
+
+    fn record(path: &Path, chunks: &[(u64, Vec<u8>)]) {
+        let base = ReceiveTime::now();
+        let recording = Recorder::new(path)
+            .with_marker_interval(Duration::ZERO)
+            .start()
+            .unwrap();
+
+        for (millis, bytes) in chunks {
+            recording.record(bytes, received(base, *millis)).unwrap();
+        }
+
+        recording.finish().unwrap();
+    }
+
+    #[test]
+    fn replay_and_seek() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        let chunks: Vec<(u64, Vec<u8>)> =
+            (0..10).map(|i| (i * 100, gps_packet(i as f64))).collect();
+        record(&path, &chunks);
+
+        let mut replay = Replay::open(&path).unwrap();
+        let mut bytes = Vec::new();
+        replay.read_to_end(&mut bytes).unwrap();
+
+        assert_eq!(
+            bytes,
+            chunks
+                .iter()
+                .flat_map(|(_, b)| b.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(replay.position(), Duration::from_millis(900));
+
+        replay.seek(Duration::from_millis(650)).unwrap();
+        let mut bytes = Vec::new();
+        replay.read_to_end(&mut bytes).unwrap();
+
+        let packets = unframe(&mut bytes);
+        assert_eq!(packets.len(), 3);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn seek_between_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        let base = ReceiveTime::now();
+
+        // A single marker ahead of all ten reads.
+        let recording = Recorder::new(&path)
+            .with_marker_interval(Duration::from_secs(60))
+            .start()
+            .unwrap();
+        for i in 0..10 {
+            recording
+                .record(&gps_packet(i as f64), received(base, i * 100))
+                .unwrap();
+        }
+        recording.finish().unwrap();
+
+        let mut replay = Replay::open(&path).unwrap();
+        replay.seek(Duration::from_millis(650)).unwrap();
+        assert_eq!(replay.position(), Duration::from_millis(700));
+
+        let mut bytes = Vec::new();
+        replay.read_to_end(&mut bytes).unwrap();
+        assert_eq!(
+            bytes,
+            [gps_packet(7.0), gps_packet(8.0), gps_packet(9.0)].concat()
+        );
+
+        assert!(replay.seek(Duration::from_millis(950)).is_err());
+        assert_eq!(replay.read(&mut [0u8; 16]).unwrap(), 0);
+    }
+
+    #[test]
+    fn paced_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+
+        // 200 ms apart by host time, 1 s apart by GPS time.
+        let chunks: Vec<(u64, Vec<u8>)> = (0..3)
+            .map(|i| (i * 100, gps_packet(i as f64 * 0.5)))
+            .collect();
+        record(&path, &chunks);
+
+        let play = |pacing, speed| {
+            let start = Instant::now();
+            let mut replay = Replay::open(&path)
+                .unwrap()
+                .with_pacing(pacing)
+                .with_speed(speed);
+            std::io::copy(&mut replay, &mut std::io::sink()).unwrap();
+            start.elapsed()
+        };
+
+        assert!(play(Pacing::Host, 2.0) >= Duration::from_millis(100));
+        assert!(play(Pacing::Gps, 5.0) >= Duration::from_millis(200));
+        assert!(play(Pacing::Fast, 1.0) < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn gps_pacing_unknown_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        let unknown = RawPacket::new(0x90, vec![packet::RawField::new(0x01, vec![0x00]).unwrap()])
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        record(&path, &[(0, unknown.clone()), (10, gps_packet(0.0))]);
+
+        let mut replay = Replay::open(&path).unwrap().with_pacing(Pacing::Gps);
+        let mut bytes = Vec::new();
+        replay.read_to_end(&mut bytes).unwrap();
+
+        assert!(bytes.starts_with(&unknown));
+    }
+
+    #[test]
+    fn host_pacing_between_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        let base = ReceiveTime::now();
+
+        // A single marker, every read follows it within the default interval.
+        let recording = Recorder::new(&path).start().unwrap();
+        for i in 0..4 {
+            recording
+                .record(&gps_packet(i as f64), received(base, i * 50))
+                .unwrap();
+        }
+        recording.finish().unwrap();
+
+        let mut replay = Replay::open(&path).unwrap().with_pacing(Pacing::Host);
+        let mut buffer = [0u8; 1];
+        let start = Instant::now();
+
+        replay.read_exact(&mut buffer).unwrap();
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        std::io::copy(&mut replay, &mut std::io::sink()).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert_eq!(replay.position(), Duration::from_millis(150));
+    }
+}
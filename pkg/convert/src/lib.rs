@@ -0,0 +1,732 @@
+use anyhow::{bail, Result};
+use capture::{rotated_path, CaptureReader, Record, MAGIC, VERSION};
+use data::Packet;
+use packet::{unframe, FieldDescription, RawPacket};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use timing::GpsTimestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Jsonl,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+            #[cfg(feature = "parquet")]
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// Splits a packet into one row per field, named `<set>.<member>` such as
+/// `imu.accelerometer`. Nested members become columns joined by `_`.
+pub fn rows(packet: &Packet) -> Vec<(String, Map<String, Value>)> {
+    let value = match serde_json::to_value(packet) {
+        Ok(Value::Object(value)) => value,
+        _ => return Vec::new(),
+    };
+
+    let set = match value.get("type").and_then(Value::as_str) {
+        Some(set) => set.to_lowercase(),
+        None => return Vec::new(),
+    };
+
+    let mut time = Map::new();
+    let gps = GpsTimestamp::from_packet(packet);
+    time.insert("gps_week".into(), gps.map(|gps| gps.week).into());
+    time.insert("gps_tow".into(), gps.map(|gps| gps.tow).into());
+
+    let mut rows = Vec::new();
+    for (member, field) in value {
+        let fields = match field {
+            Value::Object(_) => vec![field],
+            Value::Array(fields) => fields,
+            _ => continue,
+        };
+
+        for field in fields {
+            let mut row = time.clone();
+            flatten("", field, &mut row);
+            rows.push((format!("{}.{}", set, member), row));
+        }
+    }
+
+    rows
+}
+
+fn flatten(prefix: &str, value: Value, row: &mut Map<String, Value>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}_{}", prefix, key)
+        }
+    };
+
+    match value {
+        Value::Object(members) => {
+            for (key, value) in members {
+                flatten(&join(&key), value, row);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.into_iter().enumerate() {
+                flatten(&join(&index.to_string()), value, row);
+            }
+        }
+        value => {
+            row.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+/// Writes the rows of decoded packets into one file per table in a
+/// directory. Every row leads with `host_time` (UNIX seconds of the capture
+/// marker before the packet), `gps_week` and `gps_tow`.
+pub struct Converter {
+    dir: PathBuf,
+    format: Format,
+    tables: BTreeMap<String, Table>,
+    skipped: BTreeMap<u8, usize>,
+}
+
+impl Converter {
+    pub fn new<P: AsRef<Path>>(dir: P, format: Format) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            format,
+            tables: BTreeMap::new(),
+            skipped: BTreeMap::new(),
+        })
+    }
+
+    /// Decodes and adds a raw packet. Packets from descriptor sets `data`
+    /// does not know are skipped and counted instead.
+    pub fn add_raw(&mut self, host: Option<SystemTime>, packet: &RawPacket) -> Result<()> {
+        match Packet::new(packet) {
+            Ok(decoded) => self.add(host, &decoded),
+            Err(_) => {
+                *self.skipped.entry(packet.header.descriptor).or_insert(0) += 1;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn add(&mut self, host: Option<SystemTime>, packet: &Packet) -> Result<()> {
+        let host_time = host
+            .and_then(|host| host.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|host| host.as_secs_f64());
+        let descriptions = packet.descriptions();
+
+        for (name, fields) in rows(packet) {
+            let mut row = Map::new();
+            row.insert("host_time".into(), host_time.into());
+            row.extend(fields);
+
+            let table = match self.tables.get_mut(&name) {
+                Some(table) => table,
+                None => {
+                    let path = self
+                        .dir
+                        .join(format!("{}.{}", name, self.format.extension()));
+                    let member = name.split_once('.').map_or("", |(_, member)| member);
+                    let description = descriptions
+                        .iter()
+                        .find(|(name, _)| *name == member)
+                        .map(|(_, description)| description);
+                    let table = Table::create(&path, self.format, &row, description)?;
+                    self.tables.entry(name).or_insert(table)
+                }
+            };
+
+            table.write(&row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes and closes every table, returning how many packets of each
+    /// unknown descriptor set were skipped.
+    pub fn finish(self) -> Result<BTreeMap<u8, usize>> {
+        for (_, table) in self.tables {
+            table.finish()?;
+        }
+
+        Ok(self.skipped)
+    }
+}
+
+/// Converts a capture recorded by `capture::Recorder` (with its rotated
+/// files), or a file of raw device bytes, into tables in `dir`. Returns the
+/// number of skipped packets per unknown descriptor set.
+pub fn convert<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    dir: Q,
+    format: Format,
+) -> Result<BTreeMap<u8, usize>> {
+    let input = input.as_ref();
+    let mut converter = Converter::new(dir, format)?;
+
+    let mut header = [0u8; 8];
+    let is_capture = File::open(input)?.read_exact(&mut header).is_ok() && header[..7] == MAGIC[..];
+
+    if is_capture && header[7] != VERSION {
+        bail!("unsupported capture version {}", header[7]);
+    }
+
+    let mut bytes = Vec::new();
+
+    if is_capture {
+        let mut marker = None;
+        let mut index = 0;
+
+        while index == 0 || rotated_path(input, index).exists() {
+            for record in CaptureReader::open(rotated_path(input, index))? {
+                match record? {
+                    Record::Marker(record) => marker = Some(record),
+                    Record::Data(data) => {
+                        // Host time of the read, from the last marker and
+                        // the monotonic time elapsed since it.
+                        let host = marker.map(|marker| {
+                            marker.system + data.elapsed.saturating_sub(marker.elapsed)
+                        });
+                        bytes.extend(data.bytes);
+
+                        for packet in unframe(&mut bytes) {
+                            converter.add_raw(host, &packet)?;
+                        }
+                    }
+                }
+            }
+
+            index += 1;
+        }
+    } else {
+        let mut file = File::open(input)?;
+        let mut chunk = vec![0u8; 64 * 1024];
+
+        loop {
+            let count = file.read(&mut chunk)?;
+            if count == 0 {
+                break;
+            }
+
+            bytes.extend_from_slice(&chunk[..count]);
+
+            for packet in unframe(&mut bytes) {
+                converter.add_raw(None, &packet)?;
+            }
+        }
+    }
+
+    converter.finish()
+}
+
+enum Table {
+    Csv {
+        writer: Box<csv::Writer<File>>,
+        columns: Vec<String>,
+    },
+    Jsonl(BufWriter<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(Box<parquet_table::ParquetTable>),
+}
+
+impl Table {
+    /// Creates the table file, the columns are those of its first row.
+    /// `description` is the layout of the field the table holds.
+    #[cfg_attr(not(feature = "parquet"), allow(unused_variables))]
+    fn create(
+        path: &Path,
+        format: Format,
+        first: &Map<String, Value>,
+        description: Option<&FieldDescription>,
+    ) -> Result<Self> {
+        let columns: Vec<String> = first.keys().cloned().collect();
+
+        Ok(match format {
+            Format::Csv => {
+                let mut writer = Box::new(csv::Writer::from_path(path)?);
+                writer.write_record(&columns)?;
+                Self::Csv { writer, columns }
+            }
+            Format::Jsonl => Self::Jsonl(BufWriter::new(File::create(path)?)),
+            #[cfg(feature = "parquet")]
+            Format::Parquet => Self::Parquet(Box::new(parquet_table::ParquetTable::new(
+                path,
+                columns,
+                description,
+            )?)),
+        })
+    }
+
+    fn write(&mut self, row: &Map<String, Value>) -> Result<()> {
+        match self {
+            Self::Csv { writer, columns } => {
+                writer.write_record(columns.iter().map(|column| match row.get(column) {
+                    Some(Value::String(text)) => text.clone(),
+                    Some(Value::Null) | None => String::new(),
+                    Some(value) => value.to_string(),
+                }))?;
+            }
+            Self::Jsonl(writer) => {
+                serde_json::to_writer(&mut *writer, row)?;
+                writer.write_all(b"\n")?;
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet(table) => table.write(row)?,
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            Self::Csv { mut writer, .. } => writer.flush()?,
+            Self::Jsonl(mut writer) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(table) => table.finish()?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_table {
+    use anyhow::Result;
+    use arrow_array::{
+        ArrayRef, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
+        Int8Array, RecordBatch, StringArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+    };
+    use arrow_schema::{DataType, Field, Schema};
+    use packet::FieldDescription;
+    use parquet::arrow::ArrowWriter;
+    use serde_json::{Map, Value};
+    use std::collections::BTreeMap;
+    use std::convert::TryFrom;
+    use std::fs::File;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    const BATCH_ROWS: usize = 8192;
+
+    /// Buffers rows into record batches. Column types follow the member
+    /// types of the field layout, flags and enums are stored by name. Any
+    /// other column takes the type of its first non-null value in the first
+    /// batch, or float when it is null throughout.
+    pub struct ParquetTable {
+        path: PathBuf,
+        columns: Vec<String>,
+        types: BTreeMap<String, DataType>,
+        rows: Vec<Vec<Value>>,
+        writer: Option<(ArrowWriter<File>, Arc<Schema>)>,
+    }
+
+    impl ParquetTable {
+        pub fn new(
+            path: &Path,
+            columns: Vec<String>,
+            description: Option<&FieldDescription>,
+        ) -> Result<Self> {
+            File::create(path)?;
+
+            let mut types = BTreeMap::new();
+            types.insert("host_time".to_string(), DataType::Float64);
+            types.insert("gps_week".to_string(), DataType::UInt16);
+            types.insert("gps_tow".to_string(), DataType::Float64);
+
+            for member in description
+                .iter()
+                .flat_map(|description| description.members)
+            {
+                member_types(member.name.to_string(), member.ty, &mut types);
+            }
+
+            Ok(Self {
+                path: path.to_path_buf(),
+                columns,
+                types,
+                rows: Vec::new(),
+                writer: None,
+            })
+        }
+
+        pub fn write(&mut self, row: &Map<String, Value>) -> Result<()> {
+            self.rows.push(
+                self.columns
+                    .iter()
+                    .map(|column| row.get(column).cloned().unwrap_or(Value::Null))
+                    .collect(),
+            );
+
+            if self.rows.len() >= BATCH_ROWS {
+                self.flush()?;
+            }
+
+            Ok(())
+        }
+
+        pub fn finish(mut self) -> Result<()> {
+            self.flush()?;
+
+            if let Some((writer, _)) = self.writer.take() {
+                writer.close()?;
+            }
+
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            if self.rows.is_empty() {
+                return Ok(());
+            }
+
+            if self.writer.is_none() {
+                let fields: Vec<Field> = self
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| {
+                        let data_type = self.types.get(column).cloned().unwrap_or_else(|| {
+                            data_type(
+                                self.rows
+                                    .iter()
+                                    .map(|row| &row[index])
+                                    .find(|v| !v.is_null()),
+                            )
+                        });
+                        Field::new(column, data_type, true)
+                    })
+                    .collect();
+
+                let schema = Arc::new(Schema::new(fields));
+                let writer = ArrowWriter::try_new(File::create(&self.path)?, schema.clone(), None)?;
+                self.writer = Some((writer, schema));
+            }
+
+            let rows = &self.rows;
+            if let Some((writer, schema)) = &mut self.writer {
+                let arrays: Vec<ArrayRef> = schema
+                    .fields()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        array(field.data_type(), rows.iter().map(|row| &row[index]))
+                    })
+                    .collect();
+
+                writer.write(&RecordBatch::try_new(schema.clone(), arrays)?)?;
+            }
+
+            self.rows.clear();
+            Ok(())
+        }
+    }
+
+    /// Column types of a member as flattened by `rows`, `[f32; 3]` becomes
+    /// three float columns suffixed `_0` to `_2`.
+    fn member_types(name: String, ty: &str, types: &mut BTreeMap<String, DataType>) {
+        let array = ty
+            .strip_prefix('[')
+            .and_then(|ty| ty.strip_suffix(']'))
+            .and_then(|ty| ty.rsplit_once(';'))
+            .and_then(|(inner, count)| Some((inner.trim(), count.trim().parse::<usize>().ok()?)));
+
+        if let Some((inner, count)) = array {
+            for index in 0..count {
+                member_types(format!("{}_{}", name, index), inner, types);
+            }
+
+            return;
+        }
+
+        let data_type = match ty {
+            "bool" => DataType::Boolean,
+            "u8" => DataType::UInt8,
+            "u16" => DataType::UInt16,
+            "u32" => DataType::UInt32,
+            "u64" => DataType::UInt64,
+            "i8" => DataType::Int8,
+            "i16" => DataType::Int16,
+            "i32" => DataType::Int32,
+            "i64" => DataType::Int64,
+            "f32" => DataType::Float32,
+            "f64" => DataType::Float64,
+            _ => DataType::Utf8,
+        };
+
+        types.insert(name, data_type);
+    }
+
+    fn data_type(value: Option<&Value>) -> DataType {
+        match value {
+            Some(Value::Bool(_)) => DataType::Boolean,
+            Some(Value::String(_)) => DataType::Utf8,
+            Some(Value::Number(number)) if number.is_i64() => DataType::Int64,
+            Some(Value::Number(number)) if number.is_u64() => DataType::UInt64,
+            _ => DataType::Float64,
+        }
+    }
+
+    /// Integer values, null when missing or out of the column's range.
+    fn integers<'a, T: TryFrom<i128> + 'a>(
+        values: impl Iterator<Item = &'a Value> + 'a,
+    ) -> impl Iterator<Item = Option<T>> + 'a {
+        values.map(|value| {
+            let value = match value {
+                Value::Number(number) => number
+                    .as_u64()
+                    .map(i128::from)
+                    .or_else(|| number.as_i64().map(i128::from))?,
+                _ => return None,
+            };
+
+            T::try_from(value).ok()
+        })
+    }
+
+    fn array<'a>(data_type: &DataType, values: impl Iterator<Item = &'a Value> + 'a) -> ArrayRef {
+        match data_type {
+            DataType::Boolean => Arc::new(values.map(Value::as_bool).collect::<BooleanArray>()),
+            DataType::UInt8 => Arc::new(integers::<u8>(values).collect::<UInt8Array>()),
+            DataType::UInt16 => Arc::new(integers::<u16>(values).collect::<UInt16Array>()),
+            DataType::UInt32 => Arc::new(integers::<u32>(values).collect::<UInt32Array>()),
+            DataType::UInt64 => Arc::new(integers::<u64>(values).collect::<UInt64Array>()),
+            DataType::Int8 => Arc::new(integers::<i8>(values).collect::<Int8Array>()),
+            DataType::Int16 => Arc::new(integers::<i16>(values).collect::<Int16Array>()),
+            DataType::Int32 => Arc::new(integers::<i32>(values).collect::<Int32Array>()),
+            DataType::Int64 => Arc::new(integers::<i64>(values).collect::<Int64Array>()),
+            DataType::Float32 => Arc::new(
+                values
+                    .map(|value| value.as_f64().map(|value| value as f32))
+                    .collect::<Float32Array>(),
+            ),
+            DataType::Utf8 => Arc::new(
+                values
+                    .map(|value| match value {
+                        Value::Null => None,
+                        Value::String(text) => Some(text.clone()),
+                        value => Some(value.to_string()),
+                    })
+                    .collect::<StringArray>(),
+            ),
+            _ => Arc::new(values.map(Value::as_f64).collect::<Float64Array>()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data::imu_data::{ImuPacket, ScaledAccelerometerVector};
+    use data::shared_data::{GpsTimestamp, GpsTimestampFlags};
+    use std::time::Duration;
+
+    fn imu_packet() -> Packet {
+        let mut packet = ImuPacket {
+            accelerometer: Some(ScaledAccelerometerVector {
+                x: 0.5,
+                y: -0.25,
+                z: 1.0,
+            }),
+            ..Default::default()
+        };
+        packet.shared.gps_timestamp = Some(GpsTimestamp {
+            tow: 12.5,
+            week: 2200,
+            flags: GpsTimestampFlags::all(),
+        });
+
+        Packet::IMU(packet)
+    }
+
+    #[test]
+    fn packet_rows() {
+        let rows = rows(&imu_packet());
+        let names: Vec<&str> = rows.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(names, ["imu.gps_timestamp", "imu.accelerometer"]);
+
+        let accelerometer = &rows[1].1;
+        let columns: Vec<&str> = accelerometer.keys().map(String::as_str).collect();
+        assert_eq!(columns, ["gps_week", "gps_tow", "x", "y", "z"]);
+        assert_eq!(accelerometer["gps_tow"], 12.5);
+        assert_eq!(accelerometer["y"], -0.25);
+    }
+
+    #[test]
+    fn raw_to_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("raw.bin");
+
+        let packet = imu_packet().to_raw_packet().unwrap();
+        let bytes = packet::PacketComponent::to_bytes(&packet).unwrap();
+        std::fs::write(&input, [bytes.clone(), bytes].concat()).unwrap();
+
+        convert(&input, dir.path().join("out"), Format::Csv).unwrap();
+
+        let csv = std::fs::read_to_string(dir.path().join("out/imu.accelerometer.csv")).unwrap();
+        assert_eq!(
+            csv,
+            "host_time,gps_week,gps_tow,x,y,z\n,2200,12.5,0.5,-0.25,1.0\n,2200,12.5,0.5,-0.25,1.0\n"
+        );
+    }
+
+    #[test]
+    fn capture_host_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("capture.bin");
+
+        let packet = imu_packet().to_raw_packet().unwrap();
+        let bytes = packet::PacketComponent::to_bytes(&packet).unwrap();
+
+        let base = packet::ReceiveTime::now();
+        let recording = capture::Recorder::new(&input)
+            .with_marker_interval(Duration::from_secs(60))
+            .start()
+            .unwrap();
+        recording.record(&bytes, base).unwrap();
+        recording
+            .record(
+                &bytes,
+                packet::ReceiveTime {
+                    monotonic: base.monotonic + Duration::from_millis(250),
+                    system: base.system + Duration::from_millis(250),
+                },
+            )
+            .unwrap();
+        recording.finish().unwrap();
+
+        convert(&input, dir.path().join("out"), Format::Jsonl).unwrap();
+
+        let jsonl =
+            std::fs::read_to_string(dir.path().join("out/imu.accelerometer.jsonl")).unwrap();
+        let times: Vec<f64> = jsonl
+            .lines()
+            .map(|line| {
+                let row: Value = serde_json::from_str(line).unwrap();
+                row["host_time"].as_f64().unwrap()
+            })
+            .collect();
+
+        assert_eq!(times.len(), 2);
+        assert!((times[1] - times[0] - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn other_capture_version_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("capture.bin");
+        std::fs::write(&input, [&MAGIC[..], &[VERSION + 1, 0x01, 0x00]].concat()).unwrap();
+
+        let error = convert(&input, dir.path().join("out"), Format::Csv).unwrap_err();
+        assert!(error.to_string().contains("unsupported capture version"));
+    }
+
+    #[test]
+    fn unknown_sets_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("raw.bin");
+
+        let known = imu_packet().to_raw_packet().unwrap();
+        let unknown =
+            RawPacket::new(0x90, vec![packet::RawField::new(0x01, vec![0x00]).unwrap()]).unwrap();
+        let bytes = [
+            packet::PacketComponent::to_bytes(&unknown).unwrap(),
+            packet::PacketComponent::to_bytes(&known).unwrap(),
+            packet::PacketComponent::to_bytes(&unknown).unwrap(),
+        ]
+        .concat();
+        std::fs::write(&input, bytes).unwrap();
+
+        let skipped = convert(&input, dir.path().join("out"), Format::Jsonl).unwrap();
+
+        assert_eq!(skipped.into_iter().collect::<Vec<_>>(), [(0x90, 2)]);
+        assert!(dir.path().join("out/imu.accelerometer.jsonl").exists());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn raw_to_parquet() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("raw.bin");
+
+        let packet = imu_packet().to_raw_packet().unwrap();
+        let bytes = packet::PacketComponent::to_bytes(&packet).unwrap();
+        std::fs::write(&input, bytes.repeat(3)).unwrap();
+
+        convert(&input, dir.path().join("out"), Format::Parquet).unwrap();
+
+        let file = File::open(dir.path().join("out/imu.accelerometer.parquet")).unwrap();
+        let metadata = SerializedFileReader::new(file).unwrap().metadata().clone();
+        let schema = metadata.file_metadata().schema_descr();
+
+        assert_eq!(metadata.file_metadata().num_rows(), 3);
+        assert_eq!(schema.num_columns(), 6);
+        assert_eq!(schema.column(3).name(), "x");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_member_types() {
+        use arrow_array::{Float32Array, UInt64Array};
+        use arrow_schema::DataType;
+        use data::shared_data::ReferenceTime;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("raw.bin");
+
+        let mut packet = imu_packet();
+        if let Packet::IMU(imu) = &mut packet {
+            imu.shared.reference_time = Some(ReferenceTime {
+                nanoseconds: u64::MAX - 1,
+            });
+        }
+        let packet = packet.to_raw_packet().unwrap();
+        std::fs::write(&input, packet::PacketComponent::to_bytes(&packet).unwrap()).unwrap();
+
+        convert(&input, dir.path().join("out"), Format::Parquet).unwrap();
+
+        let read = |name: &str| {
+            let file = File::open(dir.path().join("out").join(name)).unwrap();
+            let mut reader = ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap();
+            reader.next().unwrap().unwrap()
+        };
+
+        let batch = read("imu.reference_time.parquet");
+        let column = batch.column_by_name("nanoseconds").unwrap();
+        assert_eq!(column.data_type(), &DataType::UInt64);
+        let column = column.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(column.value(0), u64::MAX - 1);
+
+        let batch = read("imu.accelerometer.parquet");
+        let column = batch.column_by_name("x").unwrap();
+        assert_eq!(column.data_type(), &DataType::Float32);
+        let column = column.as_any().downcast_ref::<Float32Array>().unwrap();
+        assert_eq!(column.value(0), 0.5);
+        assert_eq!(
+            batch.column_by_name("gps_week").unwrap().data_type(),
+            &DataType::UInt16
+        );
+    }
+}
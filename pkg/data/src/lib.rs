@@ -1,11 +1,221 @@
 #![allow(dead_code)]
 use derive_field::{DataPacket, FieldExtract};
-use packet::Field;
 use packet::RawField;
-use serde::{self, Serialize};
+use serde::{self, Deserialize, Serialize};
+
+/// Declares an enum backed by a fixed width integer that can be used as a
+/// member of a `FieldExtract` struct. Values without a variant, such as those
+/// added by newer firmware, decode to `Unrecognized` instead of failing the
+/// whole field.
+macro_rules! field_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $repr:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:expr,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            #[serde(untagged)]
+            Unrecognized($repr),
+        }
+
+        impl From<$repr> for $name {
+            fn from(value: $repr) -> Self {
+                match value {
+                    $($value => Self::$variant,)*
+                    value => Self::Unrecognized(value),
+                }
+            }
+        }
+
+        impl From<$name> for $repr {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unrecognized(value) => value,
+                }
+            }
+        }
+
+        impl desert::FromBytesBE for $name {
+            fn from_bytes_be(src: &[u8]) -> Result<(usize, Self), desert::Error> {
+                let (size, value) = <$repr as desert::FromBytesBE>::from_bytes_be(src)?;
+                Ok((size, value.into()))
+            }
+        }
+
+        impl desert::ToBytesBE for $name {
+            fn to_bytes_be(&self) -> Result<Vec<u8>, desert::Error> {
+                <$repr as desert::ToBytesBE>::to_bytes_be(&(*self).into())
+            }
+        }
+
+        impl packet::WireSize for $name {
+            const WIRE_SIZE: usize = std::mem::size_of::<$repr>();
+        }
+    };
+}
+
+/// Declares a `bitflags` type with an accessor per flag that can be used as a
+/// member of a `FieldExtract` struct. Unknown bits are retained rather than
+/// rejected.
+macro_rules! field_flags {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident: $repr:ident {
+            $(const $flag:ident = $value:expr => $accessor:ident;)*
+        }
+    ) => {
+        bitflags::bitflags! {
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+            #[serde(transparent)]
+            pub struct $name: $repr {
+                $(const $flag = $value;)*
+            }
+        }
+
+        impl $name {
+            $(
+                pub fn $accessor(&self) -> bool {
+                    self.contains(Self::$flag)
+                }
+            )*
+        }
+
+        impl desert::FromBytesBE for $name {
+            fn from_bytes_be(src: &[u8]) -> Result<(usize, Self), desert::Error> {
+                let (size, bits) = <$repr as desert::FromBytesBE>::from_bytes_be(src)?;
+                Ok((size, Self::from_bits_retain(bits)))
+            }
+        }
+
+        impl desert::ToBytesBE for $name {
+            fn to_bytes_be(&self) -> Result<Vec<u8>, desert::Error> {
+                <$repr as desert::ToBytesBE>::to_bytes_be(&self.bits())
+            }
+        }
+
+        impl packet::WireSize for $name {
+            const WIRE_SIZE: usize = std::mem::size_of::<$repr>();
+        }
+    };
+}
+
+/// Adds accessors returning `uom` quantities for fields with a fixed unit.
+#[cfg(feature = "uom")]
+macro_rules! quantities {
+    ($(
+        $name:ident {
+            $($field:ident: $storage:ident::$quantity:ident in $module:ident::$unit:ident,)*
+        }
+    )*) => {
+        $(
+            impl $name {
+                $(
+                    pub fn $field(&self) -> uom::si::$storage::$quantity {
+                        uom::si::$storage::$quantity::new::<uom::si::$module::$unit>(self.$field)
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+/// Implements conversions from `x, y, z` fields into `nalgebra` and `glam`
+/// vectors.
+macro_rules! vector_conversions {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[cfg(feature = "nalgebra")]
+            impl From<&$name> for nalgebra::Vector3<f32> {
+                fn from(vector: &$name) -> Self {
+                    nalgebra::Vector3::new(vector.x, vector.y, vector.z)
+                }
+            }
+
+            #[cfg(feature = "glam")]
+            impl From<&$name> for glam::Vec3 {
+                fn from(vector: &$name) -> Self {
+                    glam::Vec3::new(vector.x, vector.y, vector.z)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements conversions from the attitude fields into `nalgebra` and `glam`
+/// rotations. Every conversion yields the rotation from the sensor frame to
+/// the local NED frame. MIP quaternions already follow that convention with
+/// `q0` as the scalar part, while the orientation matrix is the transpose
+/// (it maps NED vectors into the sensor frame).
+macro_rules! attitude_conversions {
+    ($quaternion:ident, $matrix:ident, $euler:ident) => {
+        #[cfg(feature = "nalgebra")]
+        impl From<&$quaternion> for nalgebra::UnitQuaternion<f32> {
+            fn from(q: &$quaternion) -> Self {
+                nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(
+                    q.q0, q.q1, q.q2, q.q3,
+                ))
+            }
+        }
+
+        #[cfg(feature = "nalgebra")]
+        impl From<&$matrix> for nalgebra::Rotation3<f32> {
+            fn from(m: &$matrix) -> Self {
+                nalgebra::Rotation3::from_matrix_unchecked(nalgebra::Matrix3::new(
+                    m.m11, m.m21, m.m31, m.m12, m.m22, m.m32, m.m13, m.m23, m.m33,
+                ))
+            }
+        }
+
+        #[cfg(feature = "nalgebra")]
+        impl From<&$euler> for nalgebra::Rotation3<f32> {
+            fn from(e: &$euler) -> Self {
+                nalgebra::Rotation3::from_euler_angles(e.roll, e.pitch, e.yaw)
+            }
+        }
+
+        #[cfg(feature = "nalgebra")]
+        impl From<&$euler> for nalgebra::UnitQuaternion<f32> {
+            fn from(e: &$euler) -> Self {
+                nalgebra::UnitQuaternion::from_euler_angles(e.roll, e.pitch, e.yaw)
+            }
+        }
+
+        #[cfg(feature = "glam")]
+        impl From<&$quaternion> for glam::Quat {
+            fn from(q: &$quaternion) -> Self {
+                glam::Quat::from_xyzw(q.q1, q.q2, q.q3, q.q0).normalize()
+            }
+        }
+
+        #[cfg(feature = "glam")]
+        impl From<&$matrix> for glam::Mat3 {
+            fn from(m: &$matrix) -> Self {
+                glam::Mat3::from_cols(
+                    glam::Vec3::new(m.m11, m.m12, m.m13),
+                    glam::Vec3::new(m.m21, m.m22, m.m23),
+                    glam::Vec3::new(m.m31, m.m32, m.m33),
+                )
+            }
+        }
+
+        #[cfg(feature = "glam")]
+        impl From<&$euler> for glam::Quat {
+            fn from(e: &$euler) -> Self {
+                glam::Quat::from_euler(glam::EulerRot::ZYX, e.yaw, e.pitch, e.roll)
+            }
+        }
+    };
+}
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
 pub enum Packet {
     BASE {},
     DM {},
@@ -17,8 +227,10 @@ pub enum Packet {
 }
 
 impl Packet {
-    pub fn new(packet: &packet::RawPacket) -> Self {
-        match packet.header.descriptor {
+    /// Decodes a packet, failing with `ParsingError::UnknownDescriptorSet`
+    /// for descriptor sets this crate does not know.
+    pub fn new(packet: &packet::RawPacket) -> anyhow::Result<Self> {
+        Ok(match packet.header.descriptor {
             0x80 => Self::IMU(imu_data::ImuPacket::from_vec(&packet.payload.fields)),
             0x81 => Self::GNSS(gnss_data::GnssPacket::from_vec(&packet.payload.fields)),
             0x82 => Self::FILTER(filter_data::FilterPacket::from_vec(&packet.payload.fields)),
@@ -26,640 +238,2794 @@ impl Packet {
             0x0C => Self::DM {},
             0x0D => Self::ESTIMATION {},
             0x7F => Self::SYSTEM {},
-            _ => panic!("Not a data packet"),
+            descriptor => {
+                return Err(error::ParsingError::UnknownDescriptorSet { descriptor }.into())
+            }
+        })
+    }
+
+    /// Encodes the packet back into a MIP packet with a valid checksum.
+    pub fn to_raw_packet(&self) -> anyhow::Result<packet::RawPacket> {
+        let (descriptor, fields) = match self {
+            Self::IMU(packet) => (0x80, packet.to_vec()?),
+            Self::GNSS(packet) => (0x81, packet.to_vec()?),
+            Self::FILTER(packet) => (0x82, packet.to_vec()?),
+            Self::BASE {} => (0x01, vec![]),
+            Self::DM {} => (0x0C, vec![]),
+            Self::ESTIMATION {} => (0x0D, vec![]),
+            Self::SYSTEM {} => (0x7F, vec![]),
+        };
+
+        packet::RawPacket::new(descriptor, fields)
+    }
+
+    /// Layout of every field type the packet's descriptor set can hold, with
+    /// the name of the member holding it.
+    pub fn descriptions(&self) -> Vec<(&'static str, packet::FieldDescription)> {
+        match self {
+            Self::IMU(_) => imu_data::ImuPacket::descriptions(),
+            Self::GNSS(_) => gnss_data::GnssPacket::descriptions(),
+            Self::FILTER(_) => filter_data::FilterPacket::descriptions(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Shared data fields (timestamps, event source) carried by the packet,
+    /// regardless of which descriptor set produced it.
+    pub fn shared(&self) -> Option<&shared_data::SharedData> {
+        match self {
+            Self::IMU(packet) => Some(&packet.shared),
+            Self::GNSS(packet) => Some(&packet.shared),
+            Self::FILTER(packet) => Some(&packet.shared),
+            _ => None,
+        }
+    }
+}
+
+/// Every field type this crate decodes, ordered by descriptor set and field
+/// descriptor. Shared fields are listed once under the wildcard set 0xFF.
+pub fn registry() -> &'static [packet::FieldDescription] {
+    static REGISTRY: std::sync::OnceLock<Vec<packet::FieldDescription>> =
+        std::sync::OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        let descriptions: std::collections::BTreeMap<(u8, u8), packet::FieldDescription> =
+            imu_data::ImuPacket::descriptions()
+                .into_iter()
+                .chain(gnss_data::GnssPacket::descriptions())
+                .chain(filter_data::FilterPacket::descriptions())
+                .map(|(_, description)| ((description.set, description.descriptor), description))
+                .collect();
+
+        descriptions.into_values().collect()
+    })
+}
+
+/// Layout of the field with a descriptor in a descriptor set, falling back to
+/// the shared fields.
+pub fn lookup(set: u8, descriptor: u8) -> Option<&'static packet::FieldDescription> {
+    let find = |set: u8| {
+        registry()
+            .iter()
+            .find(|description| description.set == set && description.descriptor == descriptor)
+    };
+
+    find(set).or_else(|| find(0xFF))
+}
+
+/// Name of the field type with a descriptor in a descriptor set.
+pub fn field_name(set: u8, descriptor: u8) -> Option<&'static str> {
+    lookup(set, descriptor).map(|description| description.name)
+}
+
+/// Hex dump of a raw packet with its fields named from the registry.
+pub fn hex_dump(packet: &packet::RawPacket) -> packet::HexDump<'_> {
+    packet.hex_dump(field_name)
+}
+
+/// `0x80 IMU: 0x04 ScaledAccelerometerVector { x: .. }, ..`, the descriptor
+/// set followed by every decoded field.
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::IMU(packet) => write!(f, "0x80 IMU: {}", packet),
+            Self::GNSS(packet) => write!(f, "0x81 GNSS: {}", packet),
+            Self::FILTER(packet) => write!(f, "0x82 FILTER: {}", packet),
+            Self::BASE {} => write!(f, "0x01 BASE"),
+            Self::DM {} => write!(f, "0x0c DM"),
+            Self::ESTIMATION {} => write!(f, "0x0d ESTIMATION"),
+            Self::SYSTEM {} => write!(f, "0x7f SYSTEM"),
+        }
+    }
+}
+
+pub mod shared_data {
+    use super::*;
+
+    /// Fields from the shared descriptor range (0xD0-0xDF). These may appear
+    /// in any data packet so their `SET_DESCRIPTOR` is the wildcard 0xFF.
+    #[derive(DataPacket, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct SharedData {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub event_source: Option<EventSource>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ticks: Option<Ticks>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub delta_ticks: Option<DeltaTicks>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gps_timestamp: Option<GpsTimestamp>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub delta_time: Option<DeltaTime>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reference_time: Option<ReferenceTime>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reference_time_delta: Option<ReferenceTimeDelta>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub external_timestamp: Option<ExternalTimestamp>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub external_time_delta: Option<ExternalTimeDelta>,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD0)]
+    pub struct EventSource {
+        pub trigger_id: u8,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD1)]
+    pub struct Ticks {
+        pub ticks: u32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD2)]
+    pub struct DeltaTicks {
+        pub ticks: u32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD3)]
+    pub struct GpsTimestamp {
+        pub tow: f64,
+        pub week: u16,
+        #[valid(TOW_VALID, WEEK_VALID)]
+        pub flags: GpsTimestampFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD4)]
+    pub struct DeltaTime {
+        pub seconds: f64,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD5)]
+    pub struct ReferenceTime {
+        pub nanoseconds: u64,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD6)]
+    pub struct ReferenceTimeDelta {
+        pub nanoseconds: u64,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD7)]
+    pub struct ExternalTimestamp {
+        pub nanoseconds: u64,
+        #[valid(VALID)]
+        pub flags: ExternalTimeFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0xFF, 0xD8)]
+    pub struct ExternalTimeDelta {
+        pub nanoseconds: u64,
+        #[valid(VALID)]
+        pub flags: ExternalTimeFlags,
+    }
+
+    field_flags! {
+        pub struct GpsTimestampFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct ExternalTimeFlags: u16 {
+            const VALID = 0x0001 => is_valid;
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use packet::{Checksum, Header, Payload, RawPacket};
+
+        #[test]
+        fn shared_in_imu_packet() {
+            let packet = RawPacket {
+                header: Header {
+                    sync_one: 0x75,
+                    sync_two: 0x65,
+                    descriptor: 0x80,
+                },
+                payload: Payload {
+                    length: 6,
+                    fields: vec![RawField {
+                        length: 6,
+                        descriptor: 0xD1,
+                        data: vec![0x00, 0x00, 0x01, 0x00],
+                    }],
+                },
+                checksum: Checksum { msb: 0, lsb: 0 },
+            };
+            let packet = Packet::new(&packet).unwrap();
+            let shared = packet.shared().unwrap();
+
+            assert_eq!(shared.ticks.as_ref().map(|t| t.ticks), Some(256));
+            assert!(shared.gps_timestamp.is_none());
         }
     }
 }
-mod imu_data {
+pub mod imu_data {
     use super::*;
+    use shared_data::SharedData;
 
-    #[derive(DataPacket, Debug, Serialize)]
+    #[derive(DataPacket, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
     pub struct ImuPacket {
+        #[serde(flatten)]
+        pub shared: SharedData,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub accelerometer: Option<ScaledAccelerometerVector>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gyro: Option<ScaledGyroVector>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub magnetometer: Option<ScaledMagnetometerVector>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pressure: Option<ScaledAmbientPressure>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub dtv: Option<DeltaThetaVector>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub dvv: Option<DeltaVelocityVector>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub orientation_matrix: Option<OrientationMatrix>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
-        accelerometer: Option<ScaledAccelerometerVector>,
+        pub quaternion: Option<Quaternion>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gyro: Option<ScaledGyroVector>,
+        pub euler_angles: Option<EulerAngles>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        magnetometer: Option<ScaledMagnetometerVector>,
+        pub north_vector: Option<StabilizedNorthVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        pressure: Option<ScaledAmbientPressure>,
+        pub up_vector: Option<StabilizedUpVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        dtv: Option<DeltaThetaVector>,
+        pub gps_correlation: Option<GpsCorrelationTimestamp>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        dvv: Option<DeltaVelocityVector>,
+        pub raw_accelerometer: Option<RawAccelerometerVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        orientation_matrix: Option<OrientationMatrix>,
+        pub raw_gyro: Option<RawGyroVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        quaternion: Option<Quaternion>,
+        pub raw_magnetometer: Option<RawMagnetometerVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        euler_angles: Option<EulerAngles>,
+        pub raw_pressure: Option<RawAmbientPressure>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        north_vector: Option<StabilizedNorthVector>,
+        pub internal_timestamp: Option<InternalTimestamp>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        up_vector: Option<StabilizedUpVector>,
+        pub pps_timestamp: Option<PpsTimestamp>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gps_correlation: Option<GpsCorrelationTimestamp>,
+        pub overrange_status: Option<OverrangeStatus>,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x01)]
+    pub struct RawAccelerometerVector {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x02)]
+    pub struct RawGyroVector {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x03)]
+    pub struct RawMagnetometerVector {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x16)]
+    pub struct RawAmbientPressure {
+        pub ambient_pressure: f32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x0E)]
+    pub struct InternalTimestamp {
+        pub ticks: u32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x0F)]
+    pub struct PpsTimestamp {
+        pub seconds: u32,
+        pub nanoseconds: u32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x80, 0x18)]
+    pub struct OverrangeStatus {
+        pub status: OverrangeFlags,
+    }
+
+    field_flags! {
+        pub struct OverrangeFlags: u16 {
+            const ACCEL_X = 0x0001 => accel_x;
+            const ACCEL_Y = 0x0002 => accel_y;
+            const ACCEL_Z = 0x0004 => accel_z;
+            const GYRO_X = 0x0010 => gyro_x;
+            const GYRO_Y = 0x0020 => gyro_y;
+            const GYRO_Z = 0x0040 => gyro_z;
+            const MAG_X = 0x0100 => mag_x;
+            const MAG_Y = 0x0200 => mag_y;
+            const MAG_Z = 0x0400 => mag_z;
+            const PRESSURE = 0x1000 => pressure;
+        }
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x04)]
     pub struct ScaledAccelerometerVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x05)]
     pub struct ScaledGyroVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x06)]
     pub struct ScaledMagnetometerVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x17)]
     pub struct ScaledAmbientPressure {
-        ambient_pressure: f32,
+        pub ambient_pressure: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x07)]
     pub struct DeltaThetaVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x08)]
     pub struct DeltaVelocityVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x09)]
     pub struct OrientationMatrix {
-        m11: f32,
-        m12: f32,
-        m13: f32,
-        m21: f32,
-        m22: f32,
-        m23: f32,
-        m31: f32,
-        m32: f32,
-        m33: f32,
-    }
-
-    #[derive(FieldExtract, Debug, Serialize)]
+        pub m11: f32,
+        pub m12: f32,
+        pub m13: f32,
+        pub m21: f32,
+        pub m22: f32,
+        pub m23: f32,
+        pub m31: f32,
+        pub m32: f32,
+        pub m33: f32,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x0A)]
     pub struct Quaternion {
-        q0: f32,
-        q1: f32,
-        q2: f32,
-        q3: f32,
+        pub q0: f32,
+        pub q1: f32,
+        pub q2: f32,
+        pub q3: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x0C)]
     pub struct EulerAngles {
-        roll: f32,
-        pitch: f32,
-        yaw: f32,
+        pub roll: f32,
+        pub pitch: f32,
+        pub yaw: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x10)]
     pub struct StabilizedNorthVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x11)]
     pub struct StabilizedUpVector {
-        x: f32,
-        y: f32,
-        z: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x80, 0x12)]
     pub struct GpsCorrelationTimestamp {
-        tow: f64,
-        week: u16,
-        flags: u16,
+        pub tow: f64,
+        pub week: u16,
+        #[valid(TIME_INITIALIZED)]
+        pub flags: GpsCorrelationFlags,
+    }
+
+    field_flags! {
+        pub struct GpsCorrelationFlags: u16 {
+            const PPS_VALID = 0x0001 => pps_valid;
+            const TIME_REFRESHED = 0x0002 => time_refreshed;
+            const TIME_INITIALIZED = 0x0004 => time_initialized;
+        }
+    }
+
+    vector_conversions!(
+        ScaledAccelerometerVector,
+        ScaledGyroVector,
+        ScaledMagnetometerVector,
+        DeltaThetaVector,
+        DeltaVelocityVector,
+        StabilizedNorthVector,
+        StabilizedUpVector,
+        RawAccelerometerVector,
+        RawGyroVector,
+        RawMagnetometerVector,
+    );
+
+    attitude_conversions!(Quaternion, OrientationMatrix, EulerAngles);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn field(descriptor: u8, data: Vec<u8>) -> RawField {
+            RawField {
+                length: data.len() as u8 + 2,
+                descriptor,
+                data,
+            }
+        }
+
+        #[test]
+        fn raw_vectors() {
+            let data = vec![
+                0x45, 0x00, 0x00, 0x00, 0xC4, 0x00, 0x00, 0x00, 0x44, 0x80, 0x00, 0x00,
+            ];
+
+            let accelerometer = RawAccelerometerVector::new(&field(0x01, data.clone())).unwrap();
+            assert_eq!(accelerometer.x, 2048.0);
+            assert_eq!(accelerometer.y, -512.0);
+            assert_eq!(accelerometer.z, 1024.0);
+
+            let gyro = RawGyroVector::new(&field(0x02, data.clone())).unwrap();
+            assert_eq!((gyro.x, gyro.y, gyro.z), (2048.0, -512.0, 1024.0));
+
+            let magnetometer = RawMagnetometerVector::new(&field(0x03, data)).unwrap();
+            assert_eq!(
+                (magnetometer.x, magnetometer.y, magnetometer.z),
+                (2048.0, -512.0, 1024.0)
+            );
+        }
+
+        #[test]
+        fn raw_pressure() {
+            let pressure =
+                RawAmbientPressure::new(&field(0x16, vec![0x44, 0x7D, 0x50, 0x00])).unwrap();
+
+            assert_eq!(pressure.ambient_pressure, 1013.25);
+        }
+
+        #[test]
+        fn internal_timestamp() {
+            let timestamp =
+                InternalTimestamp::new(&field(0x0E, vec![0x00, 0x01, 0xE2, 0x40])).unwrap();
+
+            assert_eq!(timestamp.ticks, 123_456);
+        }
+
+        #[test]
+        fn pps_timestamp() {
+            let data = vec![0x00, 0x00, 0x0E, 0x10, 0x1D, 0xCD, 0x65, 0x00];
+            let timestamp = PpsTimestamp::new(&field(0x0F, data)).unwrap();
+
+            assert_eq!(timestamp.seconds, 3600);
+            assert_eq!(timestamp.nanoseconds, 500_000_000);
+        }
+
+        #[test]
+        fn overrange_status() {
+            let status = OverrangeStatus::new(&field(0x18, vec![0x10, 0x21])).unwrap();
+
+            assert!(status.status.accel_x());
+            assert!(status.status.gyro_y());
+            assert!(status.status.pressure());
+            assert!(!status.status.mag_z());
+        }
+
+        #[test]
+        fn raw_fields_in_packet() {
+            let fields = vec![
+                field(0x0E, vec![0x00, 0x01, 0xE2, 0x40]),
+                field(0x16, vec![0x44, 0x7D, 0x50, 0x00]),
+                field(0x18, vec![0x00, 0x00]),
+            ];
+            let packet = ImuPacket::from_vec(&fields);
+
+            assert_eq!(packet.internal_timestamp.unwrap().ticks, 123_456);
+            assert_eq!(packet.raw_pressure.unwrap().ambient_pressure, 1013.25);
+            assert!(packet.overrange_status.unwrap().status.is_empty());
+            assert!(packet.raw_accelerometer.is_none());
+        }
+
+        #[cfg(all(feature = "nalgebra", feature = "glam"))]
+        #[test]
+        fn attitude_conventions() {
+            let euler = EulerAngles {
+                roll: 0.1,
+                pitch: -0.2,
+                yaw: 1.3,
+            };
+            let expected = nalgebra::Rotation3::from_euler_angles(0.1, -0.2, 1.3);
+
+            // The device reports the NED to sensor matrix.
+            let m = expected.inverse().into_inner();
+            let matrix = OrientationMatrix {
+                m11: m[(0, 0)],
+                m12: m[(0, 1)],
+                m13: m[(0, 2)],
+                m21: m[(1, 0)],
+                m22: m[(1, 1)],
+                m23: m[(1, 2)],
+                m31: m[(2, 0)],
+                m32: m[(2, 1)],
+                m33: m[(2, 2)],
+            };
+
+            let q = nalgebra::UnitQuaternion::from_rotation_matrix(&expected);
+            let quaternion = Quaternion {
+                q0: q.w,
+                q1: q.i,
+                q2: q.j,
+                q3: q.k,
+            };
+
+            let from_euler = nalgebra::Rotation3::from(&euler);
+            let from_matrix = nalgebra::Rotation3::from(&matrix);
+            let from_quaternion = nalgebra::UnitQuaternion::from(&quaternion);
+
+            assert!((from_euler.matrix() - expected.matrix()).amax() < 1e-5);
+            assert!((from_matrix.matrix() - expected.matrix()).amax() < 1e-5);
+            assert!(
+                (from_quaternion.to_rotation_matrix().matrix() - expected.matrix()).amax() < 1e-5
+            );
+
+            let glam_expected = glam::Mat3::from(&matrix);
+            assert!(
+                glam::Mat3::from_quat(glam::Quat::from(&euler)).abs_diff_eq(glam_expected, 1e-5)
+            );
+            assert!(glam::Mat3::from_quat(glam::Quat::from(&quaternion))
+                .abs_diff_eq(glam_expected, 1e-5));
+        }
+    }
+
+    #[cfg(feature = "uom")]
+    mod quantities {
+        use super::*;
+
+        quantities! {
+            ScaledAccelerometerVector {
+                x: f32::Acceleration in acceleration::standard_gravity,
+                y: f32::Acceleration in acceleration::standard_gravity,
+                z: f32::Acceleration in acceleration::standard_gravity,
+            }
+
+            ScaledGyroVector {
+                x: f32::AngularVelocity in angular_velocity::radian_per_second,
+                y: f32::AngularVelocity in angular_velocity::radian_per_second,
+                z: f32::AngularVelocity in angular_velocity::radian_per_second,
+            }
+
+            ScaledMagnetometerVector {
+                x: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+                y: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+                z: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+            }
+
+            ScaledAmbientPressure {
+                ambient_pressure: f32::Pressure in pressure::millibar,
+            }
+
+            DeltaThetaVector {
+                x: f32::Angle in angle::radian,
+                y: f32::Angle in angle::radian,
+                z: f32::Angle in angle::radian,
+            }
+
+            EulerAngles {
+                roll: f32::Angle in angle::radian,
+                pitch: f32::Angle in angle::radian,
+                yaw: f32::Angle in angle::radian,
+            }
+
+            StabilizedNorthVector {
+                x: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+                y: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+                z: f32::MagneticFluxDensity in magnetic_flux_density::gauss,
+            }
+
+            StabilizedUpVector {
+                x: f32::Acceleration in acceleration::standard_gravity,
+                y: f32::Acceleration in acceleration::standard_gravity,
+                z: f32::Acceleration in acceleration::standard_gravity,
+            }
+        }
     }
 }
 
-mod gnss_data {
+pub mod gnss_data {
     use super::*;
+    use shared_data::SharedData;
 
-    #[derive(DataPacket, Debug, Serialize)]
+    #[derive(DataPacket, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
     pub struct GnssPacket {
+        #[serde(flatten)]
+        pub shared: SharedData,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub llh: Option<LlhPosition>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
-        llh: Option<LlhPosition>,
+        pub ecef_position: Option<EcefPosition>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        ecef_position: Option<EcefPosition>,
+        pub ned_velocity: Option<NedVelocity>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        ned_velocity: Option<NedVelocity>,
+        pub ecef_velocity: Option<EcefVelocity>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        ecef_velocity: Option<EcefVelocity>,
+        pub dop_data: Option<DopData>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        dop_data: Option<DopData>,
+        pub utc_time: Option<UtcTime>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        utc_time: Option<UtcTime>,
+        pub gps_time: Option<GpsTime>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gps_time: Option<GpsTime>,
+        pub clock_information: Option<ClockInformation>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fix_information: Option<FixInformation>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub sv_information: Vec<SpaceVehicleInformation>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hardware_status: Option<HardwareStatus>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub dgnss_information: Option<DgnssInformation>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub dgnss_status: Vec<DgnssStatus>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        clock_information: Option<ClockInformation>,
+        pub leap_seconds: Option<LeapSeconds>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub satellite_status: Vec<SatelliteStatus>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub raw_observation: Vec<RawObservation>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        fix_information: Option<FixInformation>,
+        pub base_station_info: Option<BaseStationInfo>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        sv_information: Option<SpaceVehicleInformation>,
+        pub rtk_corrections_status: Option<RtkCorrectionsStatus>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub gps_ephemeris: Vec<GpsEphemeris>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub glonass_ephemeris: Vec<GlonassEphemeris>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub galileo_ephemeris: Vec<GalileoEphemeris>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub beidou_ephemeris: Vec<BeidouEphemeris>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        hardware_status: Option<HardwareStatus>,
+        pub gps_ionosphere_correction: Option<GpsIonosphereCorrection>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        dgnss_information: Option<DgnssInformation>,
+        pub galileo_ionosphere_correction: Option<GalileoIonosphereCorrection>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        dgnss_status: Option<DgnssStatus>,
+        pub beidou_ionosphere_correction: Option<BeidouIonosphereCorrection>,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x03)]
     pub struct LlhPosition {
-        latitude: f64,
-        longitude: f64,
-        hae: f64,
-        msl: f64,
-        horizontal_accuracy: f32,
-        vertical_accuracy: f32,
-        flags: u16,
+        pub latitude: f64,
+        pub longitude: f64,
+        pub hae: f64,
+        pub msl: f64,
+        pub horizontal_accuracy: f32,
+        pub vertical_accuracy: f32,
+        #[valid(LAT_LON_VALID)]
+        pub flags: LlhPositionFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x04)]
     pub struct EcefPosition {
-        x: f64,
-        y: f64,
-        z: f64,
-        position_accuracy: f32,
-        flags: u16,
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        pub position_accuracy: f32,
+        #[valid(POSITION_VALID)]
+        pub flags: EcefPositionFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x05)]
     pub struct NedVelocity {
-        north: f32,
-        east: f32,
-        down: f32,
-        speed: f32,
-        ground_speed: f32,
-        heading: f32,
-        speed_accuracy: f32,
-        heading_accuracy: f32,
-        flags: u16,
-    }
-
-    #[derive(FieldExtract, Debug, Serialize)]
+        pub north: f32,
+        pub east: f32,
+        pub down: f32,
+        pub speed: f32,
+        pub ground_speed: f32,
+        pub heading: f32,
+        pub speed_accuracy: f32,
+        pub heading_accuracy: f32,
+        #[valid(VELOCITY_VALID)]
+        pub flags: NedVelocityFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x06)]
     pub struct EcefVelocity {
-        x: f32,
-        y: f32,
-        z: f32,
-        velocity_accuracy: f32,
-        flags: f32,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        pub velocity_accuracy: f32,
+        #[valid(VELOCITY_VALID)]
+        pub flags: EcefVelocityFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x07)]
     pub struct DopData {
-        gdop: f32,
-        pdop: f32,
-        hdop: f32,
-        vdop: f32,
-        tdop: f32,
-        ndop: f32,
-        edop: f32,
-        flags: f32,
+        pub gdop: f32,
+        pub pdop: f32,
+        pub hdop: f32,
+        pub vdop: f32,
+        pub tdop: f32,
+        pub ndop: f32,
+        pub edop: f32,
+        #[valid(GDOP_VALID)]
+        pub flags: DopFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x08)]
     pub struct UtcTime {
-        year: u16,
-        month: u8,
-        day: u8,
-        hour: u8,
-        minute: u8,
-        second: u8,
-        millisecond: u32,
-        flags: u16,
+        pub year: u16,
+        pub month: u8,
+        pub day: u8,
+        pub hour: u8,
+        pub minute: u8,
+        pub second: u8,
+        pub millisecond: u32,
+        #[valid(DATE_TIME_VALID)]
+        pub flags: UtcTimeFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x09)]
     pub struct GpsTime {
-        tow: f64,
-        week: u16,
-        flags: u16,
+        pub tow: f64,
+        pub week: u16,
+        #[valid(TOW_VALID)]
+        pub flags: GpsTimeFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0A)]
     pub struct ClockInformation {
-        bias: f64,
-        drift: f64,
-        accuracy_estimate: f64,
-        flags: u16,
+        pub bias: f64,
+        pub drift: f64,
+        pub accuracy_estimate: f64,
+        #[valid(BIAS_VALID)]
+        pub flags: ClockInformationFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0B)]
     pub struct FixInformation {
-        fix_type: u8,
-        svs: u8,
-        fix_flags: u16,
-        valid_flags: u16,
+        pub fix_type: FixType,
+        pub svs: u8,
+        pub fix_flags: FixFlags,
+        #[valid(FIX_TYPE_VALID)]
+        pub valid_flags: FixInformationFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0C)]
     pub struct SpaceVehicleInformation {
-        channel: u8,
-        vehicle_id: u8,
-        carrier_noise_ratio: u16,
-        azimuth: i16,
-        elevation: i16,
-        vehicle_flags: u16,
-        valid_flags: u16,
+        pub channel: u8,
+        pub vehicle_id: u8,
+        pub carrier_noise_ratio: u16,
+        pub azimuth: i16,
+        pub elevation: i16,
+        pub vehicle_flags: u16,
+        #[valid(VEHICLE_ID_VALID)]
+        pub valid_flags: SpaceVehicleFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0D)]
     pub struct HardwareStatus {
-        sensor_state: u8,
-        antenna_state: u8,
-        antenna_power: u8,
-        flags: u16,
+        pub sensor_state: SensorState,
+        pub antenna_state: AntennaState,
+        pub antenna_power: AntennaPower,
+        #[valid(SENSOR_STATE_VALID)]
+        pub flags: HardwareStatusFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0E)]
     pub struct DgnssInformation {
-        newest_age: f32,
-        base_station_id: i16,
-        base_station_status: i16,
-        dgnss_channels: u16,
-        flags: u16,
+        pub newest_age: f32,
+        pub base_station_id: i16,
+        pub base_station_status: i16,
+        pub dgnss_channels: u16,
+        #[valid(AGE_VALID)]
+        pub flags: DgnssInformationFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x81, 0x0F)]
     pub struct DgnssStatus {
-        vehicle_id: u8,
-        age: f32,
-        pseudorange_correction: f32,
-        pseudorange_rate_correction: f32,
-        flags: u16,
+        pub vehicle_id: u8,
+        pub age: f32,
+        pub pseudorange_correction: f32,
+        pub pseudorange_rate_correction: f32,
+        #[valid(CORRECTION_VALID)]
+        pub flags: DgnssStatusFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x11)]
+    pub struct LeapSeconds {
+        pub leap_seconds: u8,
+        #[valid(LEAP_SECONDS_VALID)]
+        pub flags: LeapSecondsFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x20)]
+    pub struct SatelliteStatus {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub constellation: GnssConstellation,
+        pub satellite_id: u8,
+        pub elevation: f32,
+        pub azimuth: f32,
+        pub healthy: bool,
+        #[valid(SATELLITE_ID_VALID)]
+        pub flags: SatelliteStatusFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x22)]
+    pub struct RawObservation {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub receiver_id: u16,
+        pub tracking_channel: u8,
+        pub constellation: GnssConstellation,
+        pub satellite_id: u8,
+        pub signal_id: u8,
+        pub signal_strength: f32,
+        pub quality: SignalQuality,
+        pub pseudorange: f64,
+        pub carrier_phase: f64,
+        pub doppler: f32,
+        pub pseudorange_uncertainty: f32,
+        pub carrier_phase_uncertainty: f32,
+        pub doppler_uncertainty: f32,
+        pub lock_time: f32,
+        #[valid(SATELLITE_ID_VALID)]
+        pub flags: RawObservationFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x30)]
+    pub struct BaseStationInfo {
+        pub tow: f64,
+        pub week: u16,
+        pub ecef_position: [f64; 3],
+        pub height: f32,
+        pub station_id: u16,
+        pub indicators: BaseStationIndicators,
+        #[valid(ECEF_POSITION_VALID)]
+        pub flags: BaseStationInfoFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x31)]
+    pub struct RtkCorrectionsStatus {
+        pub tow: f64,
+        pub week: u16,
+        pub epoch_status: RtkEpochStatus,
+        pub dongle_status: u32,
+        pub gps_correction_latency: f32,
+        pub glonass_correction_latency: f32,
+        pub galileo_correction_latency: f32,
+        pub beidou_correction_latency: f32,
+        #[serde(skip)]
+        pub reserved: [u32; 4],
+        #[valid(EPOCH_STATUS_VALID)]
+        pub flags: RtkCorrectionsStatusFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x61)]
+    pub struct GpsEphemeris {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub satellite_id: u8,
+        pub health: u8,
+        pub iodc: u8,
+        pub iode: u8,
+        pub t_oc: f64,
+        pub af0: f64,
+        pub af1: f64,
+        pub af2: f64,
+        pub t_gd: f64,
+        pub isc_l1ca: f64,
+        pub isc_l2c: f64,
+        pub t_oe: f64,
+        pub semi_major_axis: f64,
+        pub semi_major_axis_rate: f64,
+        pub mean_anomaly: f64,
+        pub delta_mean_motion: f64,
+        pub delta_mean_motion_rate: f64,
+        pub eccentricity: f64,
+        pub argument_of_perigee: f64,
+        pub omega: f64,
+        pub omega_rate: f64,
+        pub inclination: f64,
+        pub inclination_rate: f64,
+        pub cic: f64,
+        pub cis: f64,
+        pub crc: f64,
+        pub crs: f64,
+        pub cuc: f64,
+        pub cus: f64,
+        #[valid(EPHEMERIS_VALID)]
+        pub flags: EphemerisFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x62)]
+    pub struct GlonassEphemeris {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub satellite_id: u8,
+        pub frequency_number: i8,
+        pub tk: u32,
+        pub tb: u32,
+        pub satellite_type: u8,
+        pub gamma: f64,
+        pub tau_n: f64,
+        pub position: [f64; 3],
+        pub velocity: [f32; 3],
+        pub acceleration: [f32; 3],
+        pub health: u8,
+        pub p: u8,
+        pub nt: u8,
+        pub delta_tau_n: f32,
+        pub ft: u8,
+        pub en: u8,
+        pub p1: u8,
+        pub p2: u8,
+        pub p3: u8,
+        pub p4: u8,
+        #[valid(EPHEMERIS_VALID)]
+        pub flags: EphemerisFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x63)]
+    pub struct GalileoEphemeris {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub satellite_id: u8,
+        pub health: u8,
+        pub iodc: u8,
+        pub iode: u8,
+        pub t_oc: f64,
+        pub af0: f64,
+        pub af1: f64,
+        pub af2: f64,
+        pub t_gd: f64,
+        pub isc_l1ca: f64,
+        pub isc_l2c: f64,
+        pub t_oe: f64,
+        pub semi_major_axis: f64,
+        pub semi_major_axis_rate: f64,
+        pub mean_anomaly: f64,
+        pub delta_mean_motion: f64,
+        pub delta_mean_motion_rate: f64,
+        pub eccentricity: f64,
+        pub argument_of_perigee: f64,
+        pub omega: f64,
+        pub omega_rate: f64,
+        pub inclination: f64,
+        pub inclination_rate: f64,
+        pub cic: f64,
+        pub cis: f64,
+        pub crc: f64,
+        pub crs: f64,
+        pub cuc: f64,
+        pub cus: f64,
+        #[valid(EPHEMERIS_VALID)]
+        pub flags: EphemerisFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x64)]
+    pub struct BeidouEphemeris {
+        pub index: u8,
+        pub count: u8,
+        pub tow: f64,
+        pub week: u16,
+        pub satellite_id: u8,
+        pub health: u8,
+        pub iodc: u8,
+        pub iode: u8,
+        pub t_oc: f64,
+        pub af0: f64,
+        pub af1: f64,
+        pub af2: f64,
+        pub t_gd: f64,
+        pub isc_l1ca: f64,
+        pub isc_l2c: f64,
+        pub t_oe: f64,
+        pub semi_major_axis: f64,
+        pub semi_major_axis_rate: f64,
+        pub mean_anomaly: f64,
+        pub delta_mean_motion: f64,
+        pub delta_mean_motion_rate: f64,
+        pub eccentricity: f64,
+        pub argument_of_perigee: f64,
+        pub omega: f64,
+        pub omega_rate: f64,
+        pub inclination: f64,
+        pub inclination_rate: f64,
+        pub cic: f64,
+        pub cis: f64,
+        pub crc: f64,
+        pub crs: f64,
+        pub cuc: f64,
+        pub cus: f64,
+        #[valid(EPHEMERIS_VALID)]
+        pub flags: EphemerisFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x71)]
+    pub struct GpsIonosphereCorrection {
+        pub tow: f64,
+        pub week: u16,
+        pub alpha: [f64; 4],
+        pub beta: [f64; 4],
+        #[valid(ALPHA_VALID, BETA_VALID)]
+        pub flags: GpsIonosphereCorrectionFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x73)]
+    pub struct GalileoIonosphereCorrection {
+        pub tow: f64,
+        pub week: u16,
+        pub alpha: [f64; 3],
+        pub disturbance_flags: u8,
+        #[valid(ALPHA_VALID, DISTURBANCE_FLAGS_VALID)]
+        pub flags: GalileoIonosphereCorrectionFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x81, 0x74)]
+    pub struct BeidouIonosphereCorrection {
+        pub tow: f64,
+        pub week: u16,
+        pub alpha: [f64; 4],
+        pub beta: [f64; 4],
+        pub alpha_correction: [f64; 4],
+        #[valid(ALPHA_VALID, BETA_VALID, ALPHA_CORRECTION_VALID)]
+        pub flags: BeidouIonosphereCorrectionFlags,
+    }
+
+    field_enum! {
+        pub enum FixType: u8 {
+            Fix3d = 0x00,
+            Fix2d = 0x01,
+            TimeOnly = 0x02,
+            None = 0x03,
+            Invalid = 0x04,
+            RtkFloat = 0x05,
+            RtkFixed = 0x06,
+            Differential = 0x07,
+        }
+    }
+
+    field_flags! {
+        pub struct FixFlags: u16 {
+            const SBAS_USED = 0x0001 => sbas_used;
+            const DGNSS_USED = 0x0002 => dgnss_used;
+        }
+    }
+
+    field_enum! {
+        pub enum SensorState: u8 {
+            Off = 0x00,
+            On = 0x01,
+            Unknown = 0x02,
+        }
+    }
+
+    field_enum! {
+        pub enum AntennaState: u8 {
+            Init = 0x01,
+            Short = 0x02,
+            Open = 0x03,
+            Good = 0x04,
+            Unknown = 0x05,
+        }
+    }
+
+    field_enum! {
+        pub enum AntennaPower: u8 {
+            Off = 0x00,
+            On = 0x01,
+            Unknown = 0x02,
+        }
+    }
+
+    field_enum! {
+        pub enum GnssConstellation: u8 {
+            Unknown = 0x00,
+            Gps = 0x01,
+            Glonass = 0x02,
+            Galileo = 0x03,
+            Beidou = 0x04,
+        }
+    }
+
+    field_enum! {
+        pub enum SignalQuality: u8 {
+            None = 0x00,
+            Searched = 0x01,
+            Acquired = 0x02,
+            Unusable = 0x03,
+            TimeLocked = 0x04,
+            FullyLocked = 0x05,
+        }
+    }
+
+    field_flags! {
+        pub struct BaseStationIndicators: u16 {
+            const GPS = 0x0001 => gps;
+            const GLONASS = 0x0002 => glonass;
+            const GALILEO = 0x0004 => galileo;
+            const BEIDOU = 0x0008 => beidou;
+            const REFERENCE_STATION = 0x0010 => reference_station;
+            const SINGLE_RECEIVER_OSCILLATOR = 0x0020 => single_receiver_oscillator;
+            const QUARTER_CYCLE_BIT_1 = 0x0040 => quarter_cycle_bit_1;
+            const QUARTER_CYCLE_BIT_2 = 0x0080 => quarter_cycle_bit_2;
+        }
+    }
+
+    field_flags! {
+        pub struct RtkEpochStatus: u16 {
+            const ANTENNA_LOCATION_RECEIVED = 0x0001 => antenna_location_received;
+            const ANTENNA_DESCRIPTION_RECEIVED = 0x0002 => antenna_description_received;
+            const GPS_RECEIVED = 0x0004 => gps_received;
+            const GLONASS_RECEIVED = 0x0008 => glonass_received;
+            const GALILEO_RECEIVED = 0x0010 => galileo_received;
+            const BEIDOU_RECEIVED = 0x0020 => beidou_received;
+            const USING_GPS_MSM = 0x0040 => using_gps_msm;
+            const USING_GLONASS_MSM = 0x0080 => using_glonass_msm;
+            const USING_GALILEO_MSM = 0x0100 => using_galileo_msm;
+            const USING_BEIDOU_MSM = 0x0200 => using_beidou_msm;
+            const USING_ASYNC_MESSAGES = 0x0400 => using_async_messages;
+        }
+    }
+
+    field_flags! {
+        pub struct LlhPositionFlags: u16 {
+            const LAT_LON_VALID = 0x0001 => lat_lon_valid;
+            const ELLIPSOID_HEIGHT_VALID = 0x0002 => ellipsoid_height_valid;
+            const MSL_HEIGHT_VALID = 0x0004 => msl_height_valid;
+            const HORIZONTAL_ACCURACY_VALID = 0x0008 => horizontal_accuracy_valid;
+            const VERTICAL_ACCURACY_VALID = 0x0010 => vertical_accuracy_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct EcefPositionFlags: u16 {
+            const POSITION_VALID = 0x0001 => position_valid;
+            const POSITION_ACCURACY_VALID = 0x0002 => position_accuracy_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct NedVelocityFlags: u16 {
+            const VELOCITY_VALID = 0x0001 => velocity_valid;
+            const SPEED_VALID = 0x0002 => speed_valid;
+            const GROUND_SPEED_VALID = 0x0004 => ground_speed_valid;
+            const HEADING_VALID = 0x0008 => heading_valid;
+            const SPEED_ACCURACY_VALID = 0x0010 => speed_accuracy_valid;
+            const HEADING_ACCURACY_VALID = 0x0020 => heading_accuracy_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct EcefVelocityFlags: u16 {
+            const VELOCITY_VALID = 0x0001 => velocity_valid;
+            const VELOCITY_ACCURACY_VALID = 0x0002 => velocity_accuracy_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct DopFlags: u16 {
+            const GDOP_VALID = 0x0001 => gdop_valid;
+            const PDOP_VALID = 0x0002 => pdop_valid;
+            const HDOP_VALID = 0x0004 => hdop_valid;
+            const VDOP_VALID = 0x0008 => vdop_valid;
+            const TDOP_VALID = 0x0010 => tdop_valid;
+            const NDOP_VALID = 0x0020 => ndop_valid;
+            const EDOP_VALID = 0x0040 => edop_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct UtcTimeFlags: u16 {
+            const DATE_TIME_VALID = 0x0001 => date_time_valid;
+            const LEAP_SECONDS_KNOWN = 0x0002 => leap_seconds_known;
+        }
+    }
+
+    field_flags! {
+        pub struct GpsTimeFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct ClockInformationFlags: u16 {
+            const BIAS_VALID = 0x0001 => bias_valid;
+            const DRIFT_VALID = 0x0002 => drift_valid;
+            const ACCURACY_ESTIMATE_VALID = 0x0004 => accuracy_estimate_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct FixInformationFlags: u16 {
+            const FIX_TYPE_VALID = 0x0001 => fix_type_valid;
+            const SVS_VALID = 0x0002 => svs_valid;
+            const FIX_FLAGS_VALID = 0x0004 => fix_flags_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct SpaceVehicleFlags: u16 {
+            const CHANNEL_VALID = 0x0001 => channel_valid;
+            const VEHICLE_ID_VALID = 0x0002 => vehicle_id_valid;
+            const CARRIER_NOISE_RATIO_VALID = 0x0004 => carrier_noise_ratio_valid;
+            const AZIMUTH_VALID = 0x0008 => azimuth_valid;
+            const ELEVATION_VALID = 0x0010 => elevation_valid;
+            const VEHICLE_FLAGS_VALID = 0x0020 => vehicle_flags_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct HardwareStatusFlags: u16 {
+            const SENSOR_STATE_VALID = 0x0001 => sensor_state_valid;
+            const ANTENNA_STATE_VALID = 0x0002 => antenna_state_valid;
+            const ANTENNA_POWER_VALID = 0x0004 => antenna_power_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct DgnssInformationFlags: u16 {
+            const AGE_VALID = 0x0001 => age_valid;
+            const BASE_STATION_ID_VALID = 0x0002 => base_station_id_valid;
+            const BASE_STATION_STATUS_VALID = 0x0004 => base_station_status_valid;
+            const CHANNELS_VALID = 0x0008 => channels_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct DgnssStatusFlags: u16 {
+            const AGE_VALID = 0x0001 => age_valid;
+            const CORRECTION_VALID = 0x0002 => correction_valid;
+            const RATE_CORRECTION_VALID = 0x0004 => rate_correction_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct LeapSecondsFlags: u16 {
+            const LEAP_SECONDS_VALID = 0x0002 => leap_seconds_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct SatelliteStatusFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const CONSTELLATION_VALID = 0x0004 => constellation_valid;
+            const SATELLITE_ID_VALID = 0x0008 => satellite_id_valid;
+            const ELEVATION_VALID = 0x0010 => elevation_valid;
+            const AZIMUTH_VALID = 0x0020 => azimuth_valid;
+            const HEALTH_VALID = 0x0040 => health_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct RawObservationFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const RECEIVER_ID_VALID = 0x0004 => receiver_id_valid;
+            const TRACKING_CHANNEL_VALID = 0x0008 => tracking_channel_valid;
+            const CONSTELLATION_VALID = 0x0010 => constellation_valid;
+            const SATELLITE_ID_VALID = 0x0020 => satellite_id_valid;
+            const SIGNAL_ID_VALID = 0x0040 => signal_id_valid;
+            const SIGNAL_STRENGTH_VALID = 0x0080 => signal_strength_valid;
+            const QUALITY_VALID = 0x0100 => quality_valid;
+            const PSEUDORANGE_VALID = 0x0200 => pseudorange_valid;
+            const CARRIER_PHASE_VALID = 0x0400 => carrier_phase_valid;
+            const DOPPLER_VALID = 0x0800 => doppler_valid;
+            const PSEUDORANGE_UNCERTAINTY_VALID = 0x1000 => pseudorange_uncertainty_valid;
+            const CARRIER_PHASE_UNCERTAINTY_VALID = 0x2000 => carrier_phase_uncertainty_valid;
+            const DOPPLER_UNCERTAINTY_VALID = 0x4000 => doppler_uncertainty_valid;
+            const LOCK_TIME_VALID = 0x8000 => lock_time_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct BaseStationInfoFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const ECEF_POSITION_VALID = 0x0004 => ecef_position_valid;
+            const HEIGHT_VALID = 0x0008 => height_valid;
+            const STATION_ID_VALID = 0x0010 => station_id_valid;
+            const INDICATORS_VALID = 0x0020 => indicators_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct RtkCorrectionsStatusFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const EPOCH_STATUS_VALID = 0x0004 => epoch_status_valid;
+            const DONGLE_STATUS_VALID = 0x0008 => dongle_status_valid;
+            const GPS_LATENCY_VALID = 0x0010 => gps_latency_valid;
+            const GLONASS_LATENCY_VALID = 0x0020 => glonass_latency_valid;
+            const GALILEO_LATENCY_VALID = 0x0040 => galileo_latency_valid;
+            const BEIDOU_LATENCY_VALID = 0x0080 => beidou_latency_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct EphemerisFlags: u16 {
+            const EPHEMERIS_VALID = 0x0001 => ephemeris_valid;
+            const MODERN_DATA_VALID = 0x0002 => modern_data_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct GpsIonosphereCorrectionFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const ALPHA_VALID = 0x0004 => alpha_valid;
+            const BETA_VALID = 0x0008 => beta_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct GalileoIonosphereCorrectionFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const ALPHA_VALID = 0x0004 => alpha_valid;
+            const DISTURBANCE_FLAGS_VALID = 0x0008 => disturbance_flags_valid;
+        }
+    }
+
+    field_flags! {
+        pub struct BeidouIonosphereCorrectionFlags: u16 {
+            const TOW_VALID = 0x0001 => tow_valid;
+            const WEEK_VALID = 0x0002 => week_valid;
+            const ALPHA_VALID = 0x0004 => alpha_valid;
+            const BETA_VALID = 0x0008 => beta_valid;
+            const ALPHA_CORRECTION_VALID = 0x0010 => alpha_correction_valid;
+        }
+    }
+
+    #[cfg(feature = "uom")]
+    mod quantities {
+        use super::*;
+
+        quantities! {
+            LlhPosition {
+                latitude: f64::Angle in angle::degree,
+                longitude: f64::Angle in angle::degree,
+                hae: f64::Length in length::meter,
+                msl: f64::Length in length::meter,
+                horizontal_accuracy: f32::Length in length::meter,
+                vertical_accuracy: f32::Length in length::meter,
+            }
+
+            EcefPosition {
+                x: f64::Length in length::meter,
+                y: f64::Length in length::meter,
+                z: f64::Length in length::meter,
+                position_accuracy: f32::Length in length::meter,
+            }
+
+            NedVelocity {
+                north: f32::Velocity in velocity::meter_per_second,
+                east: f32::Velocity in velocity::meter_per_second,
+                down: f32::Velocity in velocity::meter_per_second,
+                speed: f32::Velocity in velocity::meter_per_second,
+                ground_speed: f32::Velocity in velocity::meter_per_second,
+                heading: f32::Angle in angle::degree,
+                speed_accuracy: f32::Velocity in velocity::meter_per_second,
+                heading_accuracy: f32::Angle in angle::degree,
+            }
+
+            EcefVelocity {
+                x: f32::Velocity in velocity::meter_per_second,
+                y: f32::Velocity in velocity::meter_per_second,
+                z: f32::Velocity in velocity::meter_per_second,
+                velocity_accuracy: f32::Velocity in velocity::meter_per_second,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use packet::Field;
+
+        #[test]
+        fn galileo_ionosphere_correction() {
+            let mut data = vec![0x40, 0x59, 0, 0, 0, 0, 0, 0, 0x08, 0x00];
+            data.extend_from_slice(&1.0f64.to_be_bytes());
+            data.extend_from_slice(&2.0f64.to_be_bytes());
+            data.extend_from_slice(&3.0f64.to_be_bytes());
+            data.extend_from_slice(&[0x01, 0x00, 0x0F]);
+
+            let field = RawField {
+                length: data.len() as u8 + 2,
+                descriptor: 0x73,
+                data,
+            };
+            let mut correction = GalileoIonosphereCorrection::new(&field).unwrap();
+
+            assert_eq!(correction.tow, 100.0);
+            assert_eq!(correction.week, 2048);
+            assert_eq!(correction.alpha, [1.0, 2.0, 3.0]);
+            assert_eq!(correction.disturbance_flags, 1);
+            assert!(correction.flags.disturbance_flags_valid());
+            assert!(correction.is_valid());
+
+            correction.flags = GalileoIonosphereCorrectionFlags::from_bits_retain(0x07);
+            assert!(!correction.is_valid());
+        }
+
+        #[test]
+        fn beidou_ionosphere_correction() {
+            let mut data = vec![0x40, 0x59, 0, 0, 0, 0, 0, 0, 0x08, 0x00];
+            for value in 0..12 {
+                data.extend_from_slice(&(value as f64).to_be_bytes());
+            }
+            data.extend_from_slice(&[0x00, 0x0F]);
+
+            let field = RawField {
+                length: data.len() as u8 + 2,
+                descriptor: 0x74,
+                data,
+            };
+            let correction = BeidouIonosphereCorrection::new(&field).unwrap();
+
+            assert_eq!(correction.beta, [4.0, 5.0, 6.0, 7.0]);
+            assert_eq!(correction.alpha_correction, [8.0, 9.0, 10.0, 11.0]);
+            assert!(!correction.flags.alpha_correction_valid());
+            assert!(!correction.is_valid());
+        }
+
+        #[test]
+        fn enum_wire_size_reported() {
+            let field = RawField {
+                length: 2,
+                descriptor: 0x0B,
+                data: Vec::new(),
+            };
+            let error = field.extract::<FixType>(0).unwrap_err();
+
+            assert!(matches!(
+                error.downcast_ref(),
+                Some(error::ParsingError::SrcInsufficent {
+                    required: 1,
+                    provided: 0
+                })
+            ));
+        }
+
+        #[test]
+        fn fix_information() {
+            let field = RawField {
+                length: 10,
+                descriptor: 0x0B,
+                data: vec![0x06, 12, 0x00, 0x02, 0x00, 0x07],
+            };
+            let fix = FixInformation::new(&field).unwrap();
+
+            assert_eq!(fix.fix_type, FixType::RtkFixed);
+            assert!(fix.fix_flags.dgnss_used());
+            assert!(!fix.fix_flags.sbas_used());
+        }
+
+        #[test]
+        fn fix_information_newer_fix_type() {
+            let field = RawField {
+                length: 10,
+                descriptor: 0x0B,
+                data: vec![0x07, 9, 0x00, 0x01, 0x00, 0x07],
+            };
+            assert_eq!(
+                FixInformation::new(&field).unwrap().fix_type,
+                FixType::Differential
+            );
+
+            let field = RawField {
+                data: vec![0x0A, 9, 0x00, 0x01, 0x00, 0x07],
+                ..field
+            };
+            let fix = FixInformation::new(&field).unwrap();
+
+            assert_eq!(fix.fix_type, FixType::Unrecognized(0x0A));
+            assert_eq!(fix.svs, 9);
+        }
+
+        #[test]
+        fn repeated_space_vehicle_information() {
+            let sv = |channel: u8, flags: u8| RawField {
+                length: 14,
+                descriptor: 0x0C,
+                data: vec![channel, 10 + channel, 0, 40, 0, 90, 0, 45, 0, 1, 0, flags],
+            };
+            let packet = GnssPacket::from_vec(&vec![sv(1, 0x3F), sv(2, 0x3F), sv(3, 0x01)]);
+
+            let channels: Vec<u8> = packet.sv_information.iter().map(|sv| sv.channel).collect();
+            assert_eq!(channels, vec![1, 2, 3]);
+
+            let packet = packet.only_valid();
+            assert_eq!(packet.sv_information.len(), 2);
+        }
     }
 }
 
-mod filter_data {
+pub mod filter_data {
     use super::*;
+    use shared_data::SharedData;
 
-    #[derive(DataPacket, Debug, Serialize)]
+    #[derive(DataPacket, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
     pub struct FilterPacket {
+        #[serde(flatten)]
+        pub shared: SharedData,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub filter_status: Option<FilterStatus>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gps_time: Option<GpsTime>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub llh_position: Option<LlhPosition>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub llh_position_uncertainty: Option<LlhPositionUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ned_velocity: Option<NedVelocity>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ned_velocity_uncertainty: Option<NedVelocityUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub quanternion: Option<Quaternion>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub quanternion_uncertanity: Option<QuaternionUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub euler_angles: Option<EulerAngles>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub euler_angles_uncertainty: Option<EulerAnglesUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub orientation_matrix: Option<OrientationMatrix>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
-        filter_status: Option<FilterStatus>,
+        pub angular_rate: Option<AngularRate>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gps_time: Option<GpsTime>,
+        pub gyro_bias: Option<GyroBias>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        llh_position: Option<LlhPosition>,
+        pub gyro_bias_uncertainty: Option<GyroBiasUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        llh_position_uncertainty: Option<LlhPositionUncertainty>,
+        pub gyro_scale_factor: Option<GyroScaleFactor>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        ned_velocity: Option<NedVelocity>,
+        pub gyro_scale_factor_uncertainty: Option<GyroScaleFactorUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        ned_velocity_uncertainty: Option<NedVelocityUncertainty>,
+        pub compensated_acceleration: Option<CompensatedAcceleration>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        quanternion: Option<Quaternion>,
+        pub linear_acceleration: Option<LinearAcceleration>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        quanternion_uncertanity: Option<QuaternionUncertainty>,
+        pub accel_bias: Option<AccelBias>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        euler_angles: Option<EulerAngles>,
+        pub accel_bias_uncertainty: Option<AccelBiasUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        euler_angles_uncertainty: Option<EulerAnglesUncertainty>,
+        pub accel_scale_factor: Option<AccelScaleFactor>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        orientation_matrix: Option<OrientationMatrix>,
+        pub accel_scale_factor_uncertainty: Option<AccelScaleFactorUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        angular_rate: Option<AngularRate>,
+        pub pressure_altitude: Option<PressureAltitude>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gyro_bias: Option<GyroBias>,
+        pub standard_atmosphere_model: Option<StandardAtmosphereModel>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gyro_bias_uncertainty: Option<GyroBiasUncertainty>,
+        pub wgs84_gravity: Option<Wgs84LocalGravity>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gyro_scale_factor: Option<GyroScaleFactor>,
+        pub gravity_vector: Option<GravityVector>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        gyro_scale_factor_uncertainty: Option<GyroScaleFactorUncertainty>,
+        pub heading_update_state: Option<HeadingUpdateState>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        compensated_acceleration: Option<CompensatedAcceleration>,
+        pub magnetic_model: Option<MagneticModel>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        linear_acceleration: Option<LinearAcceleration>,
+        pub mag_hard_iron_offset: Option<MagAutoHardIronOffset>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        accel_bias: Option<AccelBias>,
+        pub mag_soft_iron_matrix: Option<MagAutoSoftIronMatrix>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        accel_bias_uncertainty: Option<AccelBiasUncertainty>,
+        pub mag_hard_iron_offset_uncertainty: Option<MagAutoHardIronOffsetUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        accel_scale_factor: Option<AccelScaleFactor>,
+        pub mag_soft_iron_matrix_uncertainty: Option<MagAutoSoftIronMatrixUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        accel_scale_factor_uncertainty: Option<AccelScaleFactorUncertainty>,
+        pub antenna_offset_correction: Option<AntennaOffsetCorrection>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        pressure_altitude: Option<PressureAltitude>,
+        pub antenna_offset_correction_uncertainty: Option<AntennaOffsetCorrectionUncertainty>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub clock_correction: Vec<ClockCorrection>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub clock_correction_uncertainty: Vec<ClockCorrectionUncertainty>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub multi_antenna_offset_correction: Vec<MultiAntennaOffsetCorrection>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub multi_antenna_offset_correction_uncertainty:
+            Vec<MultiAntennaOffsetCorrectionUncertainty>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
-        standard_atmosphere_model: Option<StandardAtmosphereModel>,
+        pub ecef_position: Option<EcefPosition>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ecef_position_uncertainty: Option<EcefPositionUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ecef_velocity: Option<EcefVelocity>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ecef_velocity_uncertainty: Option<EcefVelocityUncertainty>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub odometer_scale_factor_error: Option<OdometerScaleFactorError>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub odometer_scale_factor_error_uncertainty: Option<OdometerScaleFactorErrorUncertainty>,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub aiding_measurement_summary: Vec<AidingMeasurementSummary>,
     }
-    #[derive(FieldExtract, Debug, Serialize)]
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x10)]
     pub struct FilterStatus {
-        state: u16,
-        dynamics: u16,
-        flags: u16,
+        pub state: FilterState,
+        pub dynamics: VehicleDynamicsMode,
+        pub flags: FilterStatusFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    impl FilterStatus {
+        const ATTITUDE_NOT_INITIALIZED: u16 = 0x1000;
+        const POSITION_VELOCITY_NOT_INITIALIZED: u16 = 0x2000;
+
+        pub fn is_running_valid(&self) -> bool {
+            self.state == FilterState::RunningValid
+        }
+
+        /// While initializing the device reuses the upper status bits to
+        /// report which parts of the solution are still waiting on data.
+        pub fn attitude_initialized(&self) -> bool {
+            self.state != FilterState::Startup
+                && !(self.state == FilterState::Initialization
+                    && self.flags.bits() & Self::ATTITUDE_NOT_INITIALIZED != 0)
+        }
+
+        pub fn position_velocity_initialized(&self) -> bool {
+            self.state != FilterState::Startup
+                && !(self.state == FilterState::Initialization
+                    && self.flags.bits() & Self::POSITION_VELOCITY_NOT_INITIALIZED != 0)
+        }
+    }
+
+    field_enum! {
+        pub enum FilterState: u16 {
+            Startup = 0x00,
+            Initialization = 0x01,
+            RunningValid = 0x02,
+            RunningError = 0x03,
+        }
+    }
+
+    field_enum! {
+        pub enum VehicleDynamicsMode: u16 {
+            Portable = 0x01,
+            Automotive = 0x02,
+            Airborne = 0x03,
+        }
+    }
+
+    field_flags! {
+        /// Status flags reported while the filter is running. During
+        /// initialization use the helpers on `FilterStatus` instead, the upper
+        /// bits have a different meaning in that state.
+        pub struct FilterStatusFlags: u16 {
+            const IMU_UNAVAILABLE = 0x0001 => imu_unavailable;
+            const GNSS_UNAVAILABLE = 0x0002 => gnss_unavailable;
+            const MATRIX_SINGULARITY = 0x0008 => matrix_singularity;
+            const POSITION_COVARIANCE_WARNING = 0x0010 => position_covariance_warning;
+            const VELOCITY_COVARIANCE_WARNING = 0x0020 => velocity_covariance_warning;
+            const ATTITUDE_COVARIANCE_WARNING = 0x0040 => attitude_covariance_warning;
+            const NAN_IN_SOLUTION = 0x0080 => nan_in_solution;
+            const GYRO_BIAS_WARNING = 0x0100 => gyro_bias_warning;
+            const ACCEL_BIAS_WARNING = 0x0200 => accel_bias_warning;
+            const GYRO_SCALE_FACTOR_WARNING = 0x0400 => gyro_scale_factor_warning;
+            const ACCEL_SCALE_FACTOR_WARNING = 0x0800 => accel_scale_factor_warning;
+            const MAG_BIAS_WARNING = 0x1000 => mag_bias_warning;
+            const ANTENNA_OFFSET_CORRECTION_WARNING = 0x2000 => antenna_offset_correction_warning;
+            const HARD_IRON_OFFSET_WARNING = 0x4000 => hard_iron_offset_warning;
+            const SOFT_IRON_CORRECTION_WARNING = 0x8000 => soft_iron_correction_warning;
+        }
+    }
+
+    field_flags! {
+        /// Valid flags shared by every estimation filter output.
+        pub struct ValidFlags: u16 {
+            const VALID = 0x0001 => is_valid;
+        }
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x11)]
     pub struct GpsTime {
-        tow: f64,
-        week: u16,
-        flags: u16,
+        pub tow: f64,
+        pub week: u16,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x01)]
     pub struct LlhPosition {
-        latitude: f64,
-        longitude: f64,
-        hae: f64,
-        flags: u16,
+        pub latitude: f64,
+        pub longitude: f64,
+        pub hae: f64,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x08)]
     pub struct LlhPositionUncertainty {
-        north: f32,
-        east: f32,
-        south: f32,
-        flags: u16,
+        pub north: f32,
+        pub east: f32,
+        pub south: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x02)]
     pub struct NedVelocity {
-        north: f32,
-        east: f32,
-        down: f32,
-        flags: u16,
+        pub north: f32,
+        pub east: f32,
+        pub down: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x09)]
     pub struct NedVelocityUncertainty {
-        north: f32,
-        east: f32,
-        down: f32,
-        flags: u16,
+        pub north: f32,
+        pub east: f32,
+        pub down: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x03)]
     pub struct Quaternion {
-        q0: f32,
-        q1: f32,
-        q2: f32,
-        q3: f32,
-        flags: u16,
+        pub q0: f32,
+        pub q1: f32,
+        pub q2: f32,
+        pub q3: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x12)]
     pub struct QuaternionUncertainty {
-        q0: f32,
-        q1: f32,
-        q2: f32,
-        q3: f32,
-        flags: u16,
+        pub q0: f32,
+        pub q1: f32,
+        pub q2: f32,
+        pub q3: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x05)]
     pub struct EulerAngles {
-        roll: f32,
-        pitch: f32,
-        yaw: f32,
-        flags: u16,
+        pub roll: f32,
+        pub pitch: f32,
+        pub yaw: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x0A)]
     pub struct EulerAnglesUncertainty {
-        roll: f32,
-        pitch: f32,
-        yaw: f32,
-        flags: u16,
+        pub roll: f32,
+        pub pitch: f32,
+        pub yaw: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x04)]
     pub struct OrientationMatrix {
-        m11: f32,
-        m12: f32,
-        m13: f32,
-        m21: f32,
-        m22: f32,
-        m23: f32,
-        m31: f32,
-        m32: f32,
-        m33: f32,
-        flags: u16,
-    }
-
-    #[derive(FieldExtract, Debug, Serialize)]
+        pub m11: f32,
+        pub m12: f32,
+        pub m13: f32,
+        pub m21: f32,
+        pub m22: f32,
+        pub m23: f32,
+        pub m31: f32,
+        pub m32: f32,
+        pub m33: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x0E)]
     pub struct AngularRate {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x06)]
     pub struct GyroBias {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x0B)]
     pub struct GyroBiasUncertainty {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x16)]
     pub struct GyroScaleFactor {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x18)]
     pub struct GyroScaleFactorUncertainty {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x1C)]
     pub struct CompensatedAcceleration {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x0D)]
     pub struct LinearAcceleration {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x07)]
     pub struct AccelBias {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x0C)]
     pub struct AccelBiasUncertainty {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x17)]
     pub struct AccelScaleFactor {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x19)]
     pub struct AccelScaleFactorUncertainty {
-        x: f32,
-        y: f32,
-        z: f32,
-        flags: u16,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x21)]
     pub struct PressureAltitude {
-        altitude: f32,
-        flags: u16,
+        pub altitude: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    #[derive(FieldExtract, Debug, Serialize)]
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     #[descriptor(0x82, 0x20)]
     pub struct StandardAtmosphereModel {
-        geometric_altitude: f32,
-        geopotential_altitude: f32,
-        temperature: f32,
-        pressure: f32,
-        density: f32,
-        flags: u16,
+        pub geometric_altitude: f32,
+        pub geopotential_altitude: f32,
+        pub temperature: f32,
+        pub pressure: f32,
+        pub density: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x0F)]
+    pub struct Wgs84LocalGravity {
+        pub magnitude: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x13)]
+    pub struct GravityVector {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x14)]
+    pub struct HeadingUpdateState {
+        pub heading: f32,
+        pub heading_uncertainty: f32,
+        pub source: HeadingSource,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    field_enum! {
+        pub enum HeadingSource: u16 {
+            None = 0x00,
+            Magnetometer = 0x01,
+            GnssVelocity = 0x02,
+            External = 0x04,
+            DualAntenna = 0x08,
+        }
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x15)]
+    pub struct MagneticModel {
+        pub intensity_north: f32,
+        pub intensity_east: f32,
+        pub intensity_down: f32,
+        pub inclination: f32,
+        pub declination: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x25)]
+    pub struct MagAutoHardIronOffset {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
     }
 
-    // TODO: Implement more types starting at gravity vector (0x82, 0x13)
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x26)]
+    pub struct MagAutoSoftIronMatrix {
+        pub m11: f32,
+        pub m12: f32,
+        pub m13: f32,
+        pub m21: f32,
+        pub m22: f32,
+        pub m23: f32,
+        pub m31: f32,
+        pub m32: f32,
+        pub m33: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x28)]
+    pub struct MagAutoHardIronOffsetUncertainty {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x29)]
+    pub struct MagAutoSoftIronMatrixUncertainty {
+        pub m11: f32,
+        pub m12: f32,
+        pub m13: f32,
+        pub m21: f32,
+        pub m22: f32,
+        pub m23: f32,
+        pub m31: f32,
+        pub m32: f32,
+        pub m33: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x30)]
+    pub struct AntennaOffsetCorrection {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x31)]
+    pub struct AntennaOffsetCorrectionUncertainty {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x32)]
+    pub struct ClockCorrection {
+        pub receiver_id: u8,
+        pub bias: f32,
+        pub bias_drift: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x33)]
+    pub struct ClockCorrectionUncertainty {
+        pub receiver_id: u8,
+        pub bias: f32,
+        pub bias_drift: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x34)]
+    pub struct MultiAntennaOffsetCorrection {
+        pub receiver_id: u8,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x35)]
+    pub struct MultiAntennaOffsetCorrectionUncertainty {
+        pub receiver_id: u8,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x36)]
+    pub struct EcefPositionUncertainty {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x37)]
+    pub struct EcefVelocityUncertainty {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x40)]
+    pub struct EcefPosition {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x41)]
+    pub struct EcefVelocity {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x47)]
+    pub struct OdometerScaleFactorError {
+        pub scale_factor_error: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x48)]
+    pub struct OdometerScaleFactorErrorUncertainty {
+        pub scale_factor_error_uncertainty: f32,
+        #[valid(VALID)]
+        pub flags: ValidFlags,
+    }
+
+    #[derive(FieldExtract, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[descriptor(0x82, 0x46)]
+    pub struct AidingMeasurementSummary {
+        pub tow: f32,
+        pub source: u8,
+        pub measurement_type: AidingMeasurementType,
+        pub indicator: AidingIndicator,
+    }
+
+    field_enum! {
+        pub enum AidingMeasurementType: u8 {
+            Gnss = 0x01,
+            DualAntenna = 0x02,
+            Heading = 0x03,
+            Pressure = 0x04,
+            Magnetometer = 0x05,
+            Speed = 0x06,
+        }
+    }
+
+    field_flags! {
+        pub struct AidingIndicator: u8 {
+            const ENABLED = 0x01 => enabled;
+            const USED = 0x02 => used;
+            const RESIDUAL_HIGH_WARNING = 0x04 => residual_high_warning;
+            const SAMPLE_TIME_WARNING = 0x08 => sample_time_warning;
+            const CONFIGURATION_ERROR = 0x10 => configuration_error;
+            const MAX_MEASUREMENTS_EXCEEDED = 0x20 => max_measurements_exceeded;
+        }
+    }
+
+    vector_conversions!(
+        AngularRate,
+        GyroBias,
+        GyroBiasUncertainty,
+        GyroScaleFactor,
+        GyroScaleFactorUncertainty,
+        CompensatedAcceleration,
+        LinearAcceleration,
+        AccelBias,
+        AccelBiasUncertainty,
+        AccelScaleFactor,
+        AccelScaleFactorUncertainty,
+        GravityVector,
+        MagAutoHardIronOffset,
+        AntennaOffsetCorrection,
+        EcefVelocity,
+    );
+
+    attitude_conversions!(Quaternion, OrientationMatrix, EulerAngles);
+
+    #[cfg(feature = "uom")]
+    mod quantities {
+        use super::*;
+
+        quantities! {
+            LlhPosition {
+                latitude: f64::Angle in angle::degree,
+                longitude: f64::Angle in angle::degree,
+                hae: f64::Length in length::meter,
+            }
+
+            NedVelocity {
+                north: f32::Velocity in velocity::meter_per_second,
+                east: f32::Velocity in velocity::meter_per_second,
+                down: f32::Velocity in velocity::meter_per_second,
+            }
+
+            EulerAngles {
+                roll: f32::Angle in angle::radian,
+                pitch: f32::Angle in angle::radian,
+                yaw: f32::Angle in angle::radian,
+            }
+
+            AngularRate {
+                x: f32::AngularVelocity in angular_velocity::radian_per_second,
+                y: f32::AngularVelocity in angular_velocity::radian_per_second,
+                z: f32::AngularVelocity in angular_velocity::radian_per_second,
+            }
+
+            GyroBias {
+                x: f32::AngularVelocity in angular_velocity::radian_per_second,
+                y: f32::AngularVelocity in angular_velocity::radian_per_second,
+                z: f32::AngularVelocity in angular_velocity::radian_per_second,
+            }
+
+            CompensatedAcceleration {
+                x: f32::Acceleration in acceleration::meter_per_second_squared,
+                y: f32::Acceleration in acceleration::meter_per_second_squared,
+                z: f32::Acceleration in acceleration::meter_per_second_squared,
+            }
+
+            LinearAcceleration {
+                x: f32::Acceleration in acceleration::meter_per_second_squared,
+                y: f32::Acceleration in acceleration::meter_per_second_squared,
+                z: f32::Acceleration in acceleration::meter_per_second_squared,
+            }
+
+            AccelBias {
+                x: f32::Acceleration in acceleration::meter_per_second_squared,
+                y: f32::Acceleration in acceleration::meter_per_second_squared,
+                z: f32::Acceleration in acceleration::meter_per_second_squared,
+            }
+
+            GravityVector {
+                x: f32::Acceleration in acceleration::meter_per_second_squared,
+                y: f32::Acceleration in acceleration::meter_per_second_squared,
+                z: f32::Acceleration in acceleration::meter_per_second_squared,
+            }
+
+            PressureAltitude {
+                altitude: f32::Length in length::meter,
+            }
+
+            StandardAtmosphereModel {
+                geometric_altitude: f32::Length in length::meter,
+                geopotential_altitude: f32::Length in length::meter,
+                temperature: f32::ThermodynamicTemperature in thermodynamic_temperature::degree_celsius,
+                pressure: f32::Pressure in pressure::millibar,
+                density: f32::MassDensity in mass_density::kilogram_per_cubic_meter,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use packet::Field;
+
+        #[test]
+        fn filter_status() {
+            let field = RawField {
+                length: 8,
+                descriptor: 0x10,
+                data: vec![0x00, 0x02, 0x00, 0x01, 0x00, 0x03],
+            };
+            let status = FilterStatus::new(&field).unwrap();
+
+            assert!(status.is_running_valid());
+            assert_eq!(status.dynamics, VehicleDynamicsMode::Portable);
+            assert_eq!(
+                status.flags,
+                FilterStatusFlags::IMU_UNAVAILABLE | FilterStatusFlags::GNSS_UNAVAILABLE
+            );
+        }
+
+        #[test]
+        fn aiding_summary_newer_measurement_type() {
+            let mut data = 12.5f32.to_be_bytes().to_vec();
+            data.extend_from_slice(&[0x03, 0x21, 0x03]);
+            let fields = vec![RawField::new(0x46, data).unwrap()];
+
+            let packet = FilterPacket::from_vec(&fields);
+            let summary = &packet.aiding_measurement_summary[0];
+
+            assert_eq!(
+                summary.measurement_type,
+                AidingMeasurementType::Unrecognized(0x21)
+            );
+            assert!(summary.indicator.used());
+            assert_eq!(summary.to_raw_field().unwrap().data, fields[0].data);
+        }
+
+        #[test]
+        fn filter_status_initializing() {
+            let field = RawField {
+                length: 8,
+                descriptor: 0x10,
+                data: vec![0x00, 0x01, 0x00, 0x02, 0x20, 0x00],
+            };
+            let status = FilterStatus::new(&field).unwrap();
+
+            assert_eq!(status.state, FilterState::Initialization);
+            assert!(status.attitude_initialized());
+            assert!(!status.position_velocity_initialized());
+        }
+
+        #[test]
+        fn only_valid() {
+            let fields = vec![
+                RawField {
+                    length: 16,
+                    descriptor: 0x0E,
+                    data: vec![0; 14],
+                },
+                RawField {
+                    length: 16,
+                    descriptor: 0x06,
+                    data: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x01],
+                },
+            ];
+            let packet = FilterPacket::from_vec(&fields);
+
+            assert!(packet.angular_rate.is_some());
+            assert!(packet.gyro_bias.as_ref().is_some_and(Field::is_valid));
+
+            let packet = packet.only_valid();
+
+            assert!(packet.angular_rate.is_none());
+            assert!(packet.gyro_bias.is_some());
+        }
+
+        #[test]
+        fn repeated_per_receiver_and_source() {
+            let clock = |receiver: u8| {
+                let mut data = vec![receiver];
+                data.extend_from_slice(&(receiver as f32).to_be_bytes());
+                data.extend_from_slice(&0.0f32.to_be_bytes());
+                data.extend_from_slice(&[0x00, 0x01]);
+                RawField::new(0x32, data).unwrap()
+            };
+            let summary = |source: u8| {
+                let mut data = 12.5f32.to_be_bytes().to_vec();
+                data.extend_from_slice(&[source, 0x01, 0x03]);
+                RawField::new(0x46, data).unwrap()
+            };
+            let fields = vec![clock(1), summary(0), clock(2), summary(1)];
+
+            let packet = FilterPacket::from_vec(&fields);
+            let receivers: Vec<u8> = packet
+                .clock_correction
+                .iter()
+                .map(|clock| clock.receiver_id)
+                .collect();
+            let sources: Vec<u8> = packet
+                .aiding_measurement_summary
+                .iter()
+                .map(|summary| summary.source)
+                .collect();
+
+            assert_eq!(receivers, [1, 2]);
+            assert_eq!(packet.clock_correction[1].bias, 2.0);
+            assert_eq!(sources, [0, 1]);
+        }
+
+        #[test]
+        fn clock_correction() {
+            let field = RawField {
+                length: 13,
+                descriptor: 0x32,
+                data: vec![
+                    0x02, 0x3F, 0x80, 0x00, 0x00, 0xBF, 0x80, 0x00, 0x00, 0x00, 0x01,
+                ],
+            };
+            let clock = ClockCorrection::new(&field).unwrap();
+
+            assert_eq!(clock.receiver_id, 2);
+            assert_eq!(clock.bias, 1.0);
+            assert_eq!(clock.bias_drift, -1.0);
+            assert!(clock.is_valid());
+        }
+
+        #[cfg(feature = "uom")]
+        #[test]
+        fn euler_angle_quantities() {
+            use uom::si::angle::degree;
+
+            let mut data = Vec::new();
+            data.extend_from_slice(&std::f32::consts::FRAC_PI_2.to_be_bytes());
+            data.extend_from_slice(&0.0f32.to_be_bytes());
+            data.extend_from_slice(&std::f32::consts::PI.to_be_bytes());
+            data.extend_from_slice(&[0x00, 0x01]);
+
+            let field = RawField {
+                length: 16,
+                descriptor: 0x05,
+                data,
+            };
+            let angles = EulerAngles::new(&field).unwrap();
+
+            assert!((angles.roll().get::<degree>() - 90.0).abs() < 1e-4);
+            assert!((angles.yaw().get::<degree>() - 180.0).abs() < 1e-4);
+        }
+
+        #[test]
+        fn filter_status_unknown_state() {
+            let field = RawField {
+                length: 8,
+                descriptor: 0x10,
+                data: vec![0x00, 0x09, 0x00, 0x01, 0x00, 0x00],
+            };
+            let status = FilterStatus::new(&field).unwrap();
+
+            assert_eq!(status.state, FilterState::Unrecognized(0x09));
+            assert_eq!(status.to_raw_field().unwrap().data, field.data);
+
+            let json = serde_json::to_string(&status).unwrap();
+            assert!(json.starts_with(r#"{"state":9,"dynamics":"Portable""#));
+            assert_eq!(serde_json::from_str::<FilterStatus>(&json).unwrap(), status);
+        }
+    }
 }
+#[cfg(test)]
+mod round_trip {
+    use super::*;
+    use packet::{PacketComponent, RawPacket};
+
+    #[test]
+    fn imu_packet() {
+        let bytes = vec![
+            0x75, 0x65, 0x80, 0x5E, 0x0E, 0x12, 0x40, 0x67, 0xD2, 0x7E, 0xF9, 0xDB, 0x22, 0xD1,
+            0x00, 0x00, 0x00, 0x06, 0x12, 0x0A, 0x3C, 0xB5, 0x86, 0xAA, 0x3D, 0xBE, 0xB0, 0x7E,
+            0x3F, 0x7E, 0xD0, 0x90, 0x3C, 0x10, 0xE8, 0xAB, 0x0E, 0x0C, 0x40, 0x47, 0xAB, 0x6C,
+            0x3D, 0x2D, 0xFD, 0xDC, 0x40, 0x3D, 0x17, 0xF4, 0x0E, 0x04, 0x3D, 0x36, 0xFC, 0xEA,
+            0xBC, 0xBE, 0x8D, 0xC0, 0x3F, 0x7F, 0x96, 0xDC, 0x0E, 0x05, 0x3A, 0x0A, 0x45, 0x73,
+            0x3A, 0xFB, 0x74, 0x4F, 0x3A, 0x6E, 0x7B, 0x95, 0x0E, 0x06, 0xBE, 0xD5, 0x4B, 0x19,
+            0x3D, 0x9D, 0x18, 0xC7, 0xBB, 0xE2, 0xCB, 0xE8, 0x06, 0x17, 0x44, 0x53, 0x1B, 0xB8,
+            0x3D, 0x55,
+        ];
+        let packet = Packet::new(&RawPacket::from_bytes(&bytes).unwrap()).unwrap();
+
+        let encoded = packet.to_raw_packet().unwrap().to_bytes().unwrap();
+        assert_eq!(encoded.len(), bytes.len());
+
+        let decoded = Packet::new(&RawPacket::from_bytes(&encoded).unwrap()).unwrap();
+        assert_eq!(decoded, packet);
+    }
+
+    #[test]
+    fn registry() {
+        let scaled_accel = lookup(0x80, 0x04).unwrap();
+
+        assert_eq!(scaled_accel.name, "ScaledAccelerometerVector");
+        assert_eq!(scaled_accel.size, 12);
+        assert_eq!(scaled_accel.members[2].name, "z");
+        assert_eq!(scaled_accel.members[2].ty, "f32");
+        assert_eq!(scaled_accel.members[2].offset, 8);
+
+        assert_eq!(lookup(0x82, 0xD3).unwrap().name, "GpsTimestamp");
+        assert_eq!(lookup(0x80, 0x7E), None);
+
+        for description in super::registry() {
+            let end = description
+                .members
+                .last()
+                .map_or(0, |member| member.offset + member.size);
+
+            assert_eq!(end, description.size, "{}", description.name);
+            assert_eq!(
+                super::registry()
+                    .iter()
+                    .filter(|other| other.name == description.name && other.set == description.set)
+                    .count(),
+                1,
+                "{}",
+                description.name
+            );
+        }
+    }
+
+    #[test]
+    fn field_length() {
+        use imu_data::ScaledAccelerometerVector;
+
+        assert_eq!(ScaledAccelerometerVector::EXPECTED_LEN, 12);
+
+        for length in [0, 1, 11, 13, 253] {
+            let field = RawField::new(0x04, vec![0x3F; length]).unwrap();
+            let error = ScaledAccelerometerVector::new(&field).unwrap_err();
+
+            assert!(matches!(
+                error.downcast_ref::<error::ParsingError>(),
+                Some(error::ParsingError::FieldLength {
+                    descriptor: 0x04,
+                    expected: 12,
+                    got,
+                }) if *got == length
+            ));
+        }
+
+        let packet =
+            imu_data::ImuPacket::from_vec(&vec![RawField::new(0x04, vec![0x3F; 11]).unwrap()]);
+        assert_eq!(packet.accelerometer, None);
+    }
+
+    #[test]
+    fn filter_status_field() {
+        let field = RawField::new(0x10, vec![0x00, 0x02, 0x00, 0x03, 0x00, 0x02]).unwrap();
+        let status = filter_data::FilterStatus::new(&field).unwrap();
+
+        assert_eq!(status.to_raw_field().unwrap().data, field.data);
+    }
+}
+
+#[cfg(test)]
+mod formatting {
+    use super::*;
+    use packet::{PacketComponent, RawPacket};
+
+    #[test]
+    fn display() {
+        let packet = Packet::IMU(imu_data::ImuPacket {
+            accelerometer: Some(imu_data::ScaledAccelerometerVector {
+                x: 0.5,
+                y: 0.0,
+                z: -1.0,
+            }),
+            shared: shared_data::SharedData {
+                ticks: Some(shared_data::Ticks { ticks: 42 }),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        assert_eq!(
+            packet.to_string(),
+            "0x80 IMU: 0xd1 Ticks { ticks: 42 }, 0x04 ScaledAccelerometerVector { x: 0.5, y: 0.0, z: -1.0 }"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let packet = Packet::FILTER(filter_data::FilterPacket {
+            filter_status: Some(filter_data::FilterStatus {
+                state: filter_data::FilterState::RunningValid,
+                dynamics: filter_data::VehicleDynamicsMode::Airborne,
+                flags: filter_data::FilterStatusFlags::GNSS_UNAVAILABLE,
+            }),
+            ..Default::default()
+        });
+
+        let json = serde_json::to_string(&packet).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"FILTER","filter_status":{"state":"RunningValid","dynamics":"Airborne","flags":"GNSS_UNAVAILABLE"}}"#
+        );
+        assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), packet);
+    }
+
+    #[test]
+    fn hex_dump() {
+        let packet = Packet::IMU(imu_data::ImuPacket {
+            accelerometer: Some(imu_data::ScaledAccelerometerVector {
+                x: 0.5,
+                y: 0.0,
+                z: -1.0,
+            }),
+            ..Default::default()
+        });
+        let raw =
+            RawPacket::from_bytes(&packet.to_raw_packet().unwrap().to_bytes().unwrap()).unwrap();
+
+        let dump = super::hex_dump(&raw).to_string();
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert!(lines[3].ends_with("field 0x04 ScaledAccelerometerVector"));
+    }
+}
+
 /*
 #[cfg(test)]
 mod tests {
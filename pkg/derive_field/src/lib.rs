@@ -1,11 +1,11 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use proc_macro_roids::{namespace_parameters, DeriveInputStructExt, FieldExt};
+use proc_macro_roids::{namespace_parameters, DeriveInputStructExt};
 use quote::quote;
 use syn::{parse_macro_input, parse_quote, DeriveInput};
 
-#[proc_macro_derive(FieldExtract, attributes(descriptor))]
+#[proc_macro_derive(FieldExtract, attributes(descriptor, valid))]
 pub fn derive_field(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
@@ -16,13 +16,44 @@ pub fn derive_field(input: TokenStream) -> TokenStream {
     let mut names = Vec::new();
     let mut types = Vec::new();
     let mut indexs = Vec::new();
+    let mut valid_checks = Vec::new();
+    let mut type_names = Vec::new();
 
     for (index, field) in fields.iter().enumerate() {
         names.push(field.ident.clone());
-        types.push(field.type_name());
+        types.push(&field.ty);
         indexs.push(index);
+
+        let ty = &field.ty;
+        type_names.push(
+            quote!(#ty)
+                .to_string()
+                .replace(" :: ", "::")
+                .replace(" ;", ";")
+                .replace("[ ", "[")
+                .replace(" ]", "]"),
+        );
+
+        let valid_bits = namespace_parameters(&field.attrs, &parse_quote!(valid));
+        if !valid_bits.is_empty() {
+            let name = &field.ident;
+            let ty = &field.ty;
+            valid_checks.push(quote! {
+                self.#name.contains(#(<#ty>::#valid_bits)|*)
+            });
+        }
     }
 
+    let is_valid = if valid_checks.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn is_valid(&self) -> bool {
+                #(#valid_checks)&&*
+            }
+        }
+    };
+
     let params = namespace_parameters(&input.attrs, &parse_quote!(descriptor));
     let set_desc = &params[0];
     let data_desc = &params[1];
@@ -33,7 +64,7 @@ pub fn derive_field(input: TokenStream) -> TokenStream {
             pub const OFFSETS: &'static [usize ; #fields_count] = &{
                 let sizes = [
                     #(
-                        std::mem::size_of::<#types>()
+                        <#types as packet::WireSize>::WIRE_SIZE
                     ,)*
                 ];
 
@@ -47,13 +78,55 @@ pub fn derive_field(input: TokenStream) -> TokenStream {
             };
 
 
+            pub const DESCRIPTION: packet::FieldDescription = packet::FieldDescription {
+                name: stringify!(#struct_name),
+                set: #set_desc,
+                descriptor: #data_desc,
+                size: #struct_name::EXPECTED_LEN,
+                members: &[
+                    #(
+                        packet::MemberDescription {
+                            name: stringify!(#names),
+                            ty: #type_names,
+                            offset: #struct_name::OFFSETS[#indexs],
+                            size: <#types as packet::WireSize>::WIRE_SIZE,
+                        }
+                    ,)*
+                ],
+            };
+
+            /// Length of the field data, without the length and descriptor
+            /// bytes.
+            pub const EXPECTED_LEN: usize = 0 #(+ <#types as packet::WireSize>::WIRE_SIZE)*;
+
             pub fn new(field: &packet::RawField) -> anyhow::Result<Self> {
+                if field.data.len() != Self::EXPECTED_LEN {
+                    return Err(error::ParsingError::FieldLength {
+                        descriptor: field.descriptor,
+                        expected: Self::EXPECTED_LEN,
+                        got: field.data.len(),
+                    }
+                    .into());
+                }
+
                 Ok(Self {
                     #(
                         #names: field.extract::<#types>(#struct_name::OFFSETS[#indexs])?
                     ,)*
                 })
             }
+
+            /// Encodes the members big-endian at `OFFSETS`.
+            pub fn to_raw_field(&self) -> anyhow::Result<packet::RawField> {
+                let mut data = vec![0u8; Self::EXPECTED_LEN];
+
+                #(
+                    desert::ToBytesBE::write_bytes_be(&self.#names, &mut data[#struct_name::OFFSETS[#indexs]..])
+                        .map_err(|e| anyhow::anyhow!(e))?;
+                )*
+
+                packet::RawField::new(<Self as packet::Field>::DATA_DESCRIPTOR, data)
+            }
         }
 
         impl std::convert::TryFrom<packet::RawField> for #struct_name {
@@ -67,6 +140,8 @@ pub fn derive_field(input: TokenStream) -> TokenStream {
         impl packet::Field for #struct_name {
             const SET_DESCRIPTOR: u8 = #set_desc;
             const DATA_DESCRIPTOR: u8 = #data_desc;
+
+            #is_valid
         }
     };
 
@@ -82,35 +157,155 @@ pub fn derive_packet(input: TokenStream) -> TokenStream {
     let struct_name = input.ident.clone();
 
     let mut names = vec![];
-    let mut inner_types = vec![];
+    let mut decoders = vec![];
+    let mut validators = vec![];
+    let mut encoders = vec![];
+    let mut displays = vec![];
+    let mut descriptions = vec![];
 
     for field in fields.iter() {
-        names.push(&field.ident);
+        let name = &field.ident;
+        names.push(name);
 
-        if let syn::Type::Path(tp) = &field.ty {
-            if let syn::PathArguments::AngleBracketed(ab) = &tp.path.segments[0].arguments {
-                inner_types.push(ab.args[0].clone());
-            }
+        // `Option<T>` members hold the last field with a descriptor, `Vec<T>`
+        // members every field with it, any other member is a nested
+        // `DataPacket` decoded from the same list of fields.
+        if let Some(inner) = wrapped_type(&field.ty, "Option") {
+            descriptions.push(quote! {
+                descriptions.push((stringify!(#name), <#inner>::DESCRIPTION))
+            });
+            encoders.push(quote! {
+                if let Some(field) = &self.#name {
+                    fields.push(field.to_raw_field()?);
+                }
+            });
+            decoders.push(quote! {
+                field_map
+                    .get(&<#inner as packet::Field>::DATA_DESCRIPTOR)
+                    .and_then(|f| f.last())
+                    .and_then(|f| <#inner>::new(f).ok())
+            });
+            validators.push(quote! {
+                self.#name.filter(|f| <#inner as packet::Field>::is_valid(f))
+            });
+            displays.push(quote! {
+                fields.extend(self.#name.iter().map(|f| {
+                    format!("{:#04x} {:?}", <#inner as packet::Field>::DATA_DESCRIPTOR, f)
+                }))
+            });
+        } else if let Some(inner) = wrapped_type(&field.ty, "Vec") {
+            descriptions.push(quote! {
+                descriptions.push((stringify!(#name), <#inner>::DESCRIPTION))
+            });
+            encoders.push(quote! {
+                for field in &self.#name {
+                    fields.push(field.to_raw_field()?);
+                }
+            });
+            decoders.push(quote! {
+                field_map
+                    .get(&<#inner as packet::Field>::DATA_DESCRIPTOR)
+                    .map(|f| f.iter().filter_map(|f| <#inner>::new(f).ok()).collect())
+                    .unwrap_or_default()
+            });
+            validators.push(quote! {
+                self.#name.into_iter().filter(|f| <#inner as packet::Field>::is_valid(f)).collect()
+            });
+            displays.push(quote! {
+                fields.extend(self.#name.iter().map(|f| {
+                    format!("{:#04x} {:?}", <#inner as packet::Field>::DATA_DESCRIPTOR, f)
+                }))
+            });
+        } else {
+            let ty = &field.ty;
+            descriptions.push(quote! { descriptions.extend(<#ty>::descriptions()) });
+            encoders.push(quote! { fields.extend(self.#name.to_vec()?) });
+            decoders.push(quote! { <#ty>::from_vec(fields) });
+            validators.push(quote! { self.#name.only_valid() });
+            displays.push(quote! {
+                fields.extend(Some(self.#name.to_string()).filter(|f| !f.is_empty()))
+            });
         }
     }
 
     let expanded = quote! {
         impl #struct_name {
             pub fn from_vec(fields: &Vec<RawField>) -> Self {
-                let mut field_map = std::collections::HashMap::new();
+                let mut field_map: std::collections::HashMap<u8, Vec<&RawField>> =
+                    std::collections::HashMap::new();
 
                 for field in fields {
-                    field_map.insert(field.descriptor, field);
+                    field_map.entry(field.descriptor).or_default().push(field);
+                }
+
+                Self {
+                    #(
+                        #names: #decoders
+                    ,)*
                 }
+            }
+
+            /// Encodes every present field, the inverse of `from_vec`.
+            pub fn to_vec(&self) -> anyhow::Result<Vec<RawField>> {
+                let mut fields = Vec::new();
+
+                #(
+                    #encoders;
+                )*
+
+                Ok(fields)
+            }
+
+            /// Layout of every field type the packet can hold, with the name of
+            /// the member holding it.
+            pub fn descriptions() -> Vec<(&'static str, packet::FieldDescription)> {
+                let mut descriptions = Vec::new();
+
+                #(
+                    #descriptions;
+                )*
+
+                descriptions
+            }
 
+            /// Drops every field the device did not mark as valid.
+            pub fn only_valid(self) -> Self {
                 Self {
                     #(
-                        #names: field_map.get(&#inner_types::DATA_DESCRIPTOR).and_then(|f| #inner_types::new(f).ok())
+                        #names: #validators
                     ,)*
                 }
             }
         }
+
+        /// Every present field as its descriptor and decoded value, such as
+        /// `0x04 ScaledAccelerometerVector { x: .. }`, separated by commas.
+        impl std::fmt::Display for #struct_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let mut fields: Vec<String> = Vec::new();
+
+                #(
+                    #displays;
+                )*
+
+                write!(f, "{}", fields.join(", "))
+            }
+        }
     };
 
     TokenStream::from(expanded)
 }
+
+fn wrapped_type<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::GenericArgument> {
+    if let syn::Type::Path(tp) = ty {
+        let segment = tp.path.segments.last()?;
+
+        if segment.ident == wrapper {
+            if let syn::PathArguments::AngleBracketed(ab) = &segment.arguments {
+                return ab.args.first();
+            }
+        }
+    }
+
+    None
+}
@@ -7,4 +7,17 @@ pub enum ParsingError {
 
     #[error("Packet invalid, bad checksum.")]
     BadChecksum,
+
+    #[error("Payload too long, {length} bytes does not fit in a single packet")]
+    PayloadTooLong { length: usize },
+
+    #[error("Field {descriptor:#04x} has {got} bytes of data, but expected {expected}")]
+    FieldLength {
+        descriptor: u8,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("Unrecognized descriptor set {descriptor:#04x}")]
+    UnknownDescriptorSet { descriptor: u8 },
 }
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use packet::PacketComponent;
+use parser::LordParser;
+
+fuzz_target!(|bytes: &[u8]| {
+    let reader = std::io::Cursor::new(bytes.to_vec());
+
+    let _ = LordParser::from_reader(Box::new(reader), |packet, _| {
+        let _ = packet.to_bytes();
+    })
+    .parse();
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use packet::{PacketComponent, Payload};
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = Payload::from_bytes(bytes);
+});
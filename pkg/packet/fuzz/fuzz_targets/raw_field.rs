@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use packet::{PacketComponent, RawField};
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = RawField::from_bytes(bytes);
+});
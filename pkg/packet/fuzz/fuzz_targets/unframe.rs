@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use packet::{unframe, PacketComponent};
+
+fuzz_target!(|bytes: &[u8]| {
+    let mut buffer = bytes.to_vec();
+
+    for packet in unframe(&mut buffer) {
+        let _ = packet.to_bytes();
+        let _ = packet.to_string();
+    }
+});
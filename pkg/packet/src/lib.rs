@@ -38,9 +38,58 @@ impl PacketComponent for Header {
     }
 }
 
+/// Number of bytes a field member takes up in the field data. This differs
+/// from its size in memory for enums with a fallback variant.
+pub trait WireSize {
+    const WIRE_SIZE: usize;
+}
+
+macro_rules! wire_size {
+    ($($ty:ty),*) => {
+        $(
+            impl WireSize for $ty {
+                const WIRE_SIZE: usize = std::mem::size_of::<$ty>();
+            }
+        )*
+    };
+}
+
+wire_size!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bool);
+
+impl<T: WireSize, const N: usize> WireSize for [T; N] {
+    const WIRE_SIZE: usize = T::WIRE_SIZE * N;
+}
+
 pub trait Field<T: std::convert::TryFrom<RawField, Error = anyhow::Error> = Self> {
     const DATA_DESCRIPTOR: u8;
     const SET_DESCRIPTOR: u8;
+
+    /// Whether the device marked this field as valid. Fields without valid
+    /// flags are always considered valid.
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+/// Layout of a field type, generated by the `FieldExtract` derive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    pub set: u8,
+    pub descriptor: u8,
+    /// Payload size in bytes, without the length and descriptor bytes.
+    pub size: usize,
+    pub members: &'static [MemberDescription],
+}
+
+/// Member of a field, stored big-endian at `offset` into the field data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemberDescription {
+    pub name: &'static str,
+    /// Rust type of the member as written in the struct, e.g. `[f32; 3]`.
+    pub ty: &'static str,
+    pub offset: usize,
+    pub size: usize,
 }
 
 #[derive(Debug)]
@@ -62,30 +111,59 @@ impl PacketComponent for RawField {
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        let length = bytes[0] as usize;
-        let descriptor = bytes[1];
-        let data = bytes[2..].to_vec();
+        let (length, descriptor) = match bytes {
+            [length, descriptor, ..] => (*length, *descriptor),
+            _ => {
+                return Err(ParsingError::SrcInsufficent {
+                    required: 2,
+                    provided: bytes.len(),
+                }
+                .into())
+            }
+        };
 
-        if bytes.len() != length {
+        if bytes.len() != length as usize {
             return Err(ParsingError::SrcInsufficent {
-                required: length,
+                required: length as usize,
                 provided: bytes.len(),
             }
             .into());
         }
 
         Ok(Self {
-            length: bytes[0],
+            length,
             descriptor,
-            data,
+            data: bytes[2..].to_vec(),
         })
     }
 }
 
 impl RawField {
-    pub fn extract<T: FromBytesBE>(&self, offset: usize) -> Result<T> {
-        let (_, num) =
-            T::from_bytes_be(&self.data[offset..]).map_err(|_| ParsingError::BadChecksum)?;
+    /// Builds a field, failing when its data does not fit the length byte.
+    pub fn new(descriptor: u8, data: Vec<u8>) -> Result<Self> {
+        let length = data.len() + 2;
+
+        if length > u8::MAX as usize {
+            return Err(ParsingError::PayloadTooLong { length }.into());
+        }
+
+        Ok(Self {
+            length: length as u8,
+            descriptor,
+            data,
+        })
+    }
+
+    /// Reads a big-endian value at `offset` into the data, failing when the
+    /// data is too short to hold it.
+    pub fn extract<T: FromBytesBE + WireSize>(&self, offset: usize) -> Result<T> {
+        let insufficent = || ParsingError::SrcInsufficent {
+            required: offset.saturating_add(T::WIRE_SIZE),
+            provided: self.data.len(),
+        };
+
+        let bytes = self.data.get(offset..).ok_or_else(insufficent)?;
+        let (_, num) = T::from_bytes_be(bytes).map_err(|_| insufficent())?;
         Ok(num)
     }
 }
@@ -108,22 +186,37 @@ impl PacketComponent for Payload {
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        let length = bytes[0] as usize;
+        let length = match bytes.first() {
+            Some(length) if bytes.len() == *length as usize + 1 => *length,
+            _ => {
+                return Err(ParsingError::SrcInsufficent {
+                    required: bytes.first().map_or(1, |length| *length as usize + 1),
+                    provided: bytes.len(),
+                }
+                .into())
+            }
+        };
+
         let mut offset: usize = 1;
         let mut fields: Vec<RawField> = Vec::new();
 
-        while offset < length {
-            let field_length = bytes[offset] as usize;
-            let field = RawField::from_bytes(&bytes[offset..(offset + field_length)])?;
-
-            fields.push(field);
+        while offset < bytes.len() {
+            // A field holds at least its length and descriptor, shorter
+            // lengths would never advance.
+            let field_length = (bytes[offset] as usize).max(2);
+            let field_bytes =
+                bytes
+                    .get(offset..offset + field_length)
+                    .ok_or(ParsingError::SrcInsufficent {
+                        required: field_length,
+                        provided: bytes.len() - offset,
+                    })?;
+
+            fields.push(RawField::from_bytes(field_bytes)?);
             offset += field_length;
         }
 
-        Ok(Self {
-            length: bytes[0],
-            fields,
-        })
+        Ok(Self { length, fields })
     }
 }
 
@@ -155,16 +248,46 @@ impl PacketComponent for Checksum {
 }
 
 impl Checksum {
-    fn validate_bytes(&self, bytes: &[u8]) -> bool {
+    /// Computes the Fletcher checksum of the header and payload bytes.
+    pub fn compute(bytes: &[u8]) -> Self {
         let mut byte_one: u8 = 0;
         let mut byte_two: u8 = 0;
 
-        for byte in &bytes[..bytes.len() - 2] {
+        for byte in bytes {
             byte_one = byte_one.wrapping_add(*byte);
             byte_two = byte_two.wrapping_add(byte_one);
         }
 
-        byte_one == self.msb && byte_two == self.lsb
+        Self {
+            msb: byte_one,
+            lsb: byte_two,
+        }
+    }
+
+    fn validate_bytes(&self, bytes: &[u8]) -> bool {
+        if bytes.len() < 2 {
+            return false;
+        }
+
+        let expected = Self::compute(&bytes[..bytes.len() - 2]);
+
+        expected.msb == self.msb && expected.lsb == self.lsb
+    }
+}
+
+/// Host time at which the first sync byte of a packet was read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReceiveTime {
+    pub monotonic: std::time::Instant,
+    pub system: std::time::SystemTime,
+}
+
+impl ReceiveTime {
+    pub fn now() -> Self {
+        Self {
+            monotonic: std::time::Instant::now(),
+            system: std::time::SystemTime::now(),
+        }
     }
 }
 
@@ -187,6 +310,15 @@ impl PacketComponent for RawPacket {
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        // Header, payload length and checksum.
+        if bytes.len() < 6 {
+            return Err(ParsingError::SrcInsufficent {
+                required: 6,
+                provided: bytes.len(),
+            }
+            .into());
+        }
+
         let checksum = Checksum::from_bytes(&bytes[bytes.len() - 2..])?;
 
         if !checksum.validate_bytes(bytes) {
@@ -204,9 +336,213 @@ impl PacketComponent for RawPacket {
     }
 }
 
+impl RawPacket {
+    /// Builds a packet for a descriptor set from its fields, filling in the
+    /// payload length and checksum.
+    pub fn new(descriptor: u8, fields: Vec<RawField>) -> Result<Self> {
+        let length: usize = fields.iter().map(|field| field.data.len() + 2).sum();
+
+        if length > u8::MAX as usize {
+            return Err(ParsingError::PayloadTooLong { length }.into());
+        }
+
+        let header = Header {
+            sync_one: 0x75,
+            sync_two: 0x65,
+            descriptor,
+        };
+        let payload = Payload {
+            length: length as u8,
+            fields,
+        };
+
+        let mut bytes = header.to_bytes()?;
+        bytes.append(&mut payload.to_bytes()?);
+
+        Ok(Self {
+            header,
+            payload,
+            checksum: Checksum::compute(&bytes),
+        })
+    }
+}
+
+/// Name of a MIP descriptor set.
+pub fn set_name(descriptor: u8) -> Option<&'static str> {
+    match descriptor {
+        0x01 => Some("BASE"),
+        0x0C => Some("DM"),
+        0x0D => Some("ESTIMATION"),
+        0x7F => Some("SYSTEM"),
+        0x80 => Some("IMU"),
+        0x81 => Some("GNSS"),
+        0x82 => Some("FILTER"),
+        _ => None,
+    }
+}
+
+fn write_hex(f: &mut std::fmt::Formatter, bytes: &[u8]) -> std::fmt::Result {
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            write!(f, " ")?;
+        }
+
+        write!(f, "{:02X}", byte)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `bytes` 16 to a line, with `note` aligned after the first.
+fn dump_line(f: &mut std::fmt::Formatter, bytes: &[u8], note: &str) -> std::fmt::Result {
+    for (index, chunk) in bytes.chunks(16).enumerate() {
+        write_hex(f, chunk)?;
+
+        if index == 0 {
+            write!(f, "{:width$}{}", "", note, width = 50 - 3 * chunk.len())?;
+        }
+
+        writeln!(f)?;
+    }
+
+    Ok(())
+}
+
+/// `0x04 [3D 36 FC EA]`, the field descriptor and its data.
+impl std::fmt::Display for RawField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:#04x} [", self.descriptor)?;
+        write_hex(f, &self.data)?;
+        write!(f, "]")
+    }
+}
+
+impl std::fmt::Display for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", field)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `0x80 IMU: 0x04 [..], 0x05 [..]` on one line, or with `{:#}` a hex dump
+/// with a line per packet component.
+impl std::fmt::Display for RawPacket {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.hex_dump(|_, _| None));
+        }
+
+        let descriptor = self.header.descriptor;
+        let name = set_name(descriptor).unwrap_or("UNKNOWN");
+
+        write!(f, "{:#04x} {}: {}", descriptor, name, self.payload)
+    }
+}
+
+impl RawPacket {
+    /// Hex dump with a line per packet component, naming each field with
+    /// `field_name(set, descriptor)`.
+    pub fn hex_dump(&self, field_name: fn(u8, u8) -> Option<&'static str>) -> HexDump<'_> {
+        HexDump {
+            packet: self,
+            field_name,
+        }
+    }
+}
+
+/// Annotated hex dump of a packet, see `RawPacket::hex_dump`.
+pub struct HexDump<'a> {
+    packet: &'a RawPacket,
+    field_name: fn(u8, u8) -> Option<&'static str>,
+}
+
+impl std::fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let packet = self.packet;
+        let descriptor = packet.header.descriptor;
+        let name = set_name(descriptor).unwrap_or("UNKNOWN");
+
+        dump_line(f, &[packet.header.sync_one, packet.header.sync_two], "sync")?;
+        dump_line(
+            f,
+            &[descriptor],
+            &format!("descriptor set {:#04x} {}", descriptor, name),
+        )?;
+        dump_line(
+            f,
+            &[packet.payload.length],
+            &format!("payload length {}", packet.payload.length),
+        )?;
+
+        for field in &packet.payload.fields {
+            let mut bytes = vec![field.length, field.descriptor];
+            bytes.extend(&field.data);
+
+            let note = match (self.field_name)(descriptor, field.descriptor) {
+                Some(name) => format!("field {:#04x} {}", field.descriptor, name),
+                None => format!("field {:#04x}", field.descriptor),
+            };
+            dump_line(f, &bytes, &note)?;
+        }
+
+        dump_line(f, &[packet.checksum.msb, packet.checksum.lsb], "checksum")
+    }
+}
+
+/// Takes every complete packet off the front of `bytes`, keeping a trailing
+/// partial one for the next call. Bytes that do not form a valid packet are
+/// skipped.
+pub fn unframe(bytes: &mut Vec<u8>) -> Vec<RawPacket> {
+    let mut packets = Vec::new();
+    let mut start = 0;
+
+    while let Some(sync) = bytes[start..]
+        .windows(2)
+        .position(|sync| sync == [0x75, 0x65])
+    {
+        let begin = start + sync;
+        let end = match bytes.get(begin + 3) {
+            Some(length) => begin + 6 + *length as usize,
+            None => {
+                start = begin;
+                break;
+            }
+        };
+
+        if end > bytes.len() {
+            start = begin;
+            break;
+        }
+
+        match RawPacket::from_bytes(&bytes[begin..end]) {
+            Ok(packet) => {
+                packets.push(packet);
+                start = end;
+            }
+            Err(_) => start = begin + 1,
+        }
+    }
+
+    // Without a sync in the remainder only a trailing 0x75 may start one.
+    if start < bytes.len() && bytes[start..].windows(2).all(|sync| sync != [0x75, 0x65]) {
+        start = bytes.len() - usize::from(bytes.last() == Some(&0x75));
+    }
+
+    bytes.drain(..start);
+    packets
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{PacketComponent, RawPacket};
+    use super::{unframe, Checksum, Header, PacketComponent, Payload, RawField, RawPacket};
+    use proptest::prelude::*;
 
     #[test]
     fn test_packet() {
@@ -223,4 +559,148 @@ mod tests {
 
         assert_eq!(v, RawPacket::from_bytes(&v).unwrap().to_bytes().unwrap());
     }
+
+    #[test]
+    fn test_new_packet() {
+        let fields = vec![RawField::new(0x17, vec![0x44, 0x53, 0x1B, 0xB8]).unwrap()];
+        let bytes = RawPacket::new(0x80, fields).unwrap().to_bytes().unwrap();
+
+        assert_eq!(
+            bytes,
+            vec![0x75, 0x65, 0x80, 0x06, 0x06, 0x17, 0x44, 0x53, 0x1B, 0xB8, 0xE7, 0xD7]
+        );
+        assert!(RawPacket::from_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_unframe() {
+        let packet = RawPacket::new(
+            0x80,
+            vec![RawField::new(0x17, vec![0x44, 0x53, 0x1B, 0xB8]).unwrap()],
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+
+        let mut bytes = vec![0x00, 0x75];
+        bytes.extend(&packet);
+        bytes.extend(&packet[..5]);
+
+        assert_eq!(unframe(&mut bytes).len(), 1);
+        assert_eq!(bytes, packet[..5]);
+
+        bytes.extend(&packet[5..]);
+        assert_eq!(unframe(&mut bytes).len(), 1);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_extract() {
+        let field = RawField::new(0x17, vec![0x44, 0x53, 0x1B, 0xB8]).unwrap();
+
+        assert_eq!(field.extract::<u16>(2).unwrap(), 0x1BB8);
+        assert!(field.extract::<u32>(1).is_err());
+        assert!(field.extract::<u8>(4).is_err());
+        assert!(field.extract::<u8>(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let fields = vec![
+            RawField::new(0x17, vec![0x44, 0x53, 0x1B, 0xB8]).unwrap(),
+            RawField::new(0x04, (0..18).collect()).unwrap(),
+        ];
+        let packet = RawPacket::new(0x80, fields).unwrap();
+
+        assert_eq!(
+            packet.to_string(),
+            "0x80 IMU: 0x17 [44 53 1B B8], 0x04 [00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F 10 11]"
+        );
+
+        let dump = format!("{:#}", packet);
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], format!("75 65{:44}sync", ""));
+        assert_eq!(lines[3], format!("06 17 44 53 1B B8{:32}field 0x17", ""));
+        assert_eq!(lines[5], "0E 0F 10 11");
+        assert!(lines[6].ends_with("checksum"));
+
+        let dump = packet
+            .hex_dump(|set, descriptor| match (set, descriptor) {
+                (0x80, 0x17) => Some("TemperatureStatistics"),
+                _ => None,
+            })
+            .to_string();
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert!(lines[3].ends_with("field 0x17 TemperatureStatistics"));
+        assert!(lines[4].ends_with("field 0x04"));
+    }
+
+    #[test]
+    fn test_field_too_long() {
+        assert!(RawField::new(0x04, vec![0; 253]).is_ok());
+        assert!(RawField::new(0x04, vec![0; 254]).is_err());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(RawField::from_bytes(&[]).is_err());
+        assert!(RawField::from_bytes(&[0x04]).is_err());
+        assert!(Payload::from_bytes(&[]).is_err());
+        assert!(Payload::from_bytes(&[0x04, 0x00, 0x04, 0x00, 0x00]).is_err());
+        assert!(RawPacket::from_bytes(&[0x75, 0x65]).is_err());
+
+        // A zero length field must not stall the payload.
+        let mut bytes = vec![0x75, 0x65, 0x80, 0x02, 0x00, 0x04];
+        let checksum = Checksum::compute(&bytes);
+        bytes.extend(&[checksum.msb, checksum.lsb]);
+        assert!(RawPacket::from_bytes(&bytes).is_err());
+    }
+
+    fn raw_field() -> impl Strategy<Value = RawField> {
+        (any::<u8>(), prop::collection::vec(any::<u8>(), 0..32))
+            .prop_map(|(descriptor, data)| RawField::new(descriptor, data).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..300)) {
+            let _ = Header::from_bytes(&bytes);
+            let _ = RawField::from_bytes(&bytes);
+            let _ = Payload::from_bytes(&bytes);
+            let _ = Checksum::from_bytes(&bytes);
+            let _ = RawPacket::from_bytes(&bytes);
+
+            let mut buffer = bytes;
+            unframe(&mut buffer);
+        }
+
+        #[test]
+        fn arbitrary_payload(descriptor in any::<u8>(), payload in prop::collection::vec(any::<u8>(), 0..=255)) {
+            // A valid frame around random payload bytes gets past the
+            // checksum into field parsing.
+            let mut bytes = vec![0x75, 0x65, descriptor, payload.len() as u8];
+            bytes.extend(&payload);
+            let checksum = Checksum::compute(&bytes);
+            bytes.extend(&[checksum.msb, checksum.lsb]);
+
+            let _ = RawPacket::from_bytes(&bytes);
+        }
+
+        #[test]
+        fn round_trip(descriptor in any::<u8>(), fields in prop::collection::vec(raw_field(), 0..8)) {
+            let packet = RawPacket::new(descriptor, fields).unwrap();
+            let bytes = packet.to_bytes().unwrap();
+            let parsed = RawPacket::from_bytes(&bytes).unwrap();
+
+            prop_assert_eq!(parsed.to_bytes().unwrap(), bytes.clone());
+
+            let mut buffer = bytes;
+            let packets = unframe(&mut buffer);
+            prop_assert_eq!(packets.len(), 1);
+            prop_assert!(buffer.is_empty());
+        }
+    }
 }
@@ -1,51 +1,88 @@
-use packet::{PacketComponent, RawPacket};
+use anyhow::{Context, Result};
+use capture::Recording;
+use packet::{PacketComponent, RawPacket, ReceiveTime};
 use ringbuf::RingBuffer;
 use serialport::SerialPort;
+use std::io::Read;
 
 enum State {
     SyncOne,
     SyncTwo,
     Descriptor,
     PayloadLength,
-    Data { length: u8 },
+    Data { length: u16 },
 }
 
 pub struct LordParser<F>
 where
-    F: Fn(RawPacket),
+    F: Fn(RawPacket, ReceiveTime),
 {
-    reader: Box<dyn SerialPort>,
+    reader: Box<dyn Read>,
     handler: F,
+    recording: Option<Recording>,
+    /// Whether an empty read ends parsing. Serial ports return nothing when
+    /// their timeout expires before data arrives, so they are read forever.
+    stop_at_eof: bool,
 }
 
 impl<F> LordParser<F>
 where
-    F: Fn(RawPacket),
+    F: Fn(RawPacket, ReceiveTime),
 {
     pub fn new(reader: Box<dyn SerialPort>, handler: F) -> Self {
         Self {
-            reader: reader,
+            stop_at_eof: false,
+            ..Self::from_reader(reader, handler)
+        }
+    }
+
+    /// Parses packets from any byte source, such as a `capture::Replay`,
+    /// until it runs out of bytes.
+    pub fn from_reader(reader: Box<dyn Read>, handler: F) -> Self {
+        Self {
+            reader,
             handler,
+            recording: None,
+            stop_at_eof: true,
         }
     }
 
-    pub fn parse(&mut self) {
+    /// Tees every byte read from the device into `recording`, before any
+    /// decoding.
+    pub fn with_recording(mut self, recording: Recording) -> Self {
+        self.recording = Some(recording);
+        self
+    }
+
+    /// Reads and handles packets until a reader from `from_reader` reaches
+    /// the end of its input. Serial ports are read until the process exits.
+    /// Fails on the first read error other than a timeout, or once the
+    /// recording has stopped.
+    pub fn parse(&mut self) -> Result<()> {
         let mut state = State::SyncOne;
         let buffer: RingBuffer<u8> = RingBuffer::new(512);
         let (mut producer, mut consumer) = buffer.split();
+        let mut last_read = ReceiveTime::now();
+        let mut chunk = vec![0u8; producer.capacity()];
 
         loop {
             let mut current_packet: Vec<u8> = Vec::new();
+            let mut received = last_read;
             let mut building_packet = true;
 
             while building_packet {
                 match consumer.pop() {
                     Some(curr_byte) => {
                         state = match (&state, curr_byte) {
-                            (State::SyncOne, 0x75) => State::SyncTwo,
+                            (State::SyncOne, 0x75) => {
+                                received = last_read;
+                                State::SyncTwo
+                            }
                             (State::SyncTwo, 0x65) => State::Descriptor,
                             (State::Descriptor, _) => State::PayloadLength,
-                            (State::PayloadLength, len) => State::Data { length: len + 1 },
+                            (State::PayloadLength, len) => State::Data {
+                                length: len as u16 + 1,
+                            },
                             (State::Data { length }, _) if *length == 0 => {
                                 building_packet = false;
                                 State::SyncOne
@@ -56,10 +93,29 @@ where
 
                         current_packet.push(curr_byte);
                     }
-                    None => match producer.read_from(&mut self.reader, None) {
-                        Ok(_) => (),
-                        Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => (),
-                        Err(e) => eprintln!("{:?}", e),
+                    None => match self.reader.read(&mut chunk) {
+                        Ok(0) if self.stop_at_eof => return Ok(()),
+                        Ok(0) => (),
+                        Ok(count) => {
+                            last_read = ReceiveTime::now();
+                            producer.push_slice(&chunk[..count]);
+
+                            if let Some(recording) = &self.recording {
+                                if let Err(e) = recording.record(&chunk[..count], last_read) {
+                                    // The writer's own error says why it stopped.
+                                    return Err(
+                                        match self.recording.take().map(Recording::finish) {
+                                            Some(Err(reason)) => reason,
+                                            _ => e,
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                        Err(ref e)
+                            if e.kind() == std::io::ErrorKind::TimedOut
+                                || e.kind() == std::io::ErrorKind::Interrupted => {}
+                        Err(e) => return Err(e).context("reading from the device failed"),
                     },
                 }
             }
@@ -72,8 +128,77 @@ where
                     }
                 };
 
-                (self.handler)(packet);
+                (self.handler)(packet, received);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::cell::Cell;
+    use std::io::{Error, ErrorKind};
+
+    /// Yields its bytes, then fails every read.
+    struct Failing(std::io::Cursor<Vec<u8>>);
+
+    impl Read for Failing {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.read(buf)? {
+                0 => Err(Error::new(ErrorKind::BrokenPipe, "disconnected")),
+                count => Ok(count),
             }
         }
     }
+
+    fn packet() -> Vec<u8> {
+        let field = packet::RawField::new(0x17, vec![0x44, 0x53, 0x1B, 0xB8]).unwrap();
+        RawPacket::new(0x80, vec![field])
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+    }
+
+    #[test]
+    fn longest_payload() {
+        let field = packet::RawField::new(0x17, vec![0xAB; 253]).unwrap();
+        let bytes = RawPacket::new(0x80, vec![field])
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let handled = Cell::new(0);
+
+        LordParser::from_reader(Box::new(std::io::Cursor::new(bytes)), |packet, _| {
+            assert_eq!(packet.payload.length, 0xFF);
+            handled.set(handled.get() + 1)
+        })
+        .parse()
+        .unwrap();
+
+        assert_eq!(handled.get(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..2048)) {
+            let reader = std::io::Cursor::new([bytes, packet()].concat());
+
+            prop_assert!(LordParser::from_reader(Box::new(reader), |_, _| ()).parse().is_ok());
+        }
+    }
+
+    #[test]
+    fn read_errors_end_parsing() {
+        let handled = Cell::new(0);
+        let reader = Failing(std::io::Cursor::new(packet()));
+
+        let result =
+            LordParser::from_reader(Box::new(reader), |_, _| handled.set(handled.get() + 1))
+                .parse();
+
+        assert_eq!(handled.get(), 1);
+        assert!(format!("{:#}", result.unwrap_err()).contains("disconnected"));
+    }
 }
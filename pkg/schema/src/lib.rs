@@ -0,0 +1,362 @@
+use anyhow::{anyhow, bail, Result};
+use packet::{FieldDescription, RawField, RawPacket};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::Path;
+
+/// Field layouts used to decode raw fields without a Rust type for them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSchema {
+    pub name: String,
+    pub set: u8,
+    pub descriptor: u8,
+    pub members: Vec<MemberSchema>,
+}
+
+/// A member of a field. Without an `offset` it follows the previous member.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Decoded member value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Array(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Map(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Int(value) => serializer.serialize_i64(*value),
+            Self::UInt(value) => serializer.serialize_u64(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Self::Map(members) => {
+                let mut map = serializer.serialize_map(Some(members.len()))?;
+                for (name, value) in members {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Member type, a primitive or a fixed size array of them such as `[f32; 3]`.
+#[derive(Debug, Clone, PartialEq)]
+enum MemberType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Array(Box<MemberType>, usize),
+}
+
+impl MemberType {
+    fn parse(ty: &str) -> Result<Self> {
+        let ty = ty.trim();
+
+        Ok(match ty {
+            "bool" => Self::Bool,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => {
+                let (inner, count) = ty
+                    .strip_prefix('[')
+                    .and_then(|ty| ty.strip_suffix(']'))
+                    .and_then(|ty| ty.rsplit_once(';'))
+                    .ok_or_else(|| anyhow!("unknown member type {}", ty))?;
+
+                Self::Array(Box::new(Self::parse(inner)?), count.trim().parse()?)
+            }
+        })
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::Bool | Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::U64 | Self::I64 | Self::F64 => 8,
+            Self::Array(inner, count) => inner.size().saturating_mul(*count),
+        }
+    }
+
+    fn decode(&self, field: &RawField, offset: usize) -> Result<Value> {
+        let end = offset.saturating_add(self.size());
+
+        if end > field.data.len() {
+            bail!(
+                "field {:#04x} holds {} bytes, member needs {}",
+                field.descriptor,
+                field.data.len(),
+                end
+            );
+        }
+
+        Ok(match self {
+            Self::Bool => Value::Bool(field.extract::<bool>(offset)?),
+            Self::U8 => Value::UInt(field.extract::<u8>(offset)?.into()),
+            Self::U16 => Value::UInt(field.extract::<u16>(offset)?.into()),
+            Self::U32 => Value::UInt(field.extract::<u32>(offset)?.into()),
+            Self::U64 => Value::UInt(field.extract::<u64>(offset)?),
+            Self::I8 => Value::Int(field.extract::<i8>(offset)?.into()),
+            Self::I16 => Value::Int(field.extract::<i16>(offset)?.into()),
+            Self::I32 => Value::Int(field.extract::<i32>(offset)?.into()),
+            Self::I64 => Value::Int(field.extract::<i64>(offset)?),
+            Self::F32 => Value::Float(field.extract::<f32>(offset)?.into()),
+            Self::F64 => Value::Float(field.extract::<f64>(offset)?),
+            Self::Array(inner, count) => Value::Array(
+                (0..*count)
+                    .map(|index| inner.decode(field, offset + index * inner.size()))
+                    .collect::<Result<_>>()?,
+            ),
+        })
+    }
+}
+
+impl FieldSchema {
+    /// Decodes the members of a field into a `Value::Map`.
+    pub fn decode(&self, field: &RawField) -> Result<Value> {
+        let mut offset = 0;
+        let mut members = Vec::new();
+
+        for member in &self.members {
+            let ty = MemberType::parse(&member.ty)?;
+            offset = member.offset.unwrap_or(offset);
+
+            members.push((member.name.clone(), ty.decode(field, offset)?));
+            offset = offset.saturating_add(ty.size());
+        }
+
+        Ok(Value::Map(members))
+    }
+}
+
+impl From<&FieldDescription> for FieldSchema {
+    fn from(description: &FieldDescription) -> Self {
+        Self {
+            name: description.name.to_string(),
+            set: description.set,
+            descriptor: description.descriptor,
+            members: description
+                .members
+                .iter()
+                .map(|member| MemberSchema {
+                    name: member.name.to_string(),
+                    // Flags and enums are read as the unsigned integer of
+                    // their size.
+                    ty: match MemberType::parse(member.ty) {
+                        Ok(_) => member.ty.to_string(),
+                        Err(_) => format!("u{}", member.size * 8),
+                    },
+                    offset: Some(member.offset),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Schema {
+    /// Schema of every field in `data::registry`.
+    pub fn builtin() -> Self {
+        Self {
+            fields: data::registry().iter().map(FieldSchema::from).collect(),
+        }
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Loads a `.toml` or `.json` schema file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&text),
+            Some("json") => Self::from_json(&text),
+            _ => bail!("schema {} is neither .toml nor .json", path.display()),
+        }
+    }
+
+    /// Adds the fields of `other`, replacing those with the same set and
+    /// descriptor.
+    pub fn extend(&mut self, other: Schema) {
+        for field in other.fields {
+            self.fields
+                .retain(|known| (known.set, known.descriptor) != (field.set, field.descriptor));
+            self.fields.push(field);
+        }
+    }
+
+    /// Layout of the field with a descriptor in a descriptor set, falling
+    /// back to the shared fields.
+    pub fn field(&self, set: u8, descriptor: u8) -> Option<&FieldSchema> {
+        let find = |set: u8| {
+            self.fields
+                .iter()
+                .find(|field| field.set == set && field.descriptor == descriptor)
+        };
+
+        find(set).or_else(|| find(0xFF))
+    }
+
+    /// Decodes a field of a descriptor set, `None` when the schema does not
+    /// know it.
+    pub fn decode(&self, set: u8, field: &RawField) -> Option<Result<Value>> {
+        self.field(set, field.descriptor)
+            .map(|schema| schema.decode(field))
+    }
+
+    /// Decodes every known field of a packet into a map from field name to
+    /// members.
+    pub fn decode_packet(&self, packet: &RawPacket) -> Result<Value> {
+        let mut fields = Vec::new();
+
+        for field in &packet.payload.fields {
+            if let Some(schema) = self.field(packet.header.descriptor, field.descriptor) {
+                fields.push((schema.name.clone(), schema.decode(field)?));
+            }
+        }
+
+        Ok(Value::Map(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use packet::PacketComponent;
+
+    const SCHEMA: &str = r#"
+        [[fields]]
+        name = "NewField"
+        set = 0x80
+        descriptor = 0x60
+        members = [
+            { name = "counter", type = "u16" },
+            { name = "vector", type = "[f32; 2]" },
+            { name = "flag", type = "bool", offset = 11 },
+        ]
+    "#;
+
+    #[test]
+    fn file_schema() {
+        let schema = Schema::from_toml(SCHEMA).unwrap();
+
+        let mut data = vec![0x01, 0x02];
+        data.extend(1.5f32.to_be_bytes());
+        data.extend((-2.0f32).to_be_bytes());
+        data.extend([0x00, 0x01]);
+        let field = RawField::new(0x60, data).unwrap();
+
+        let value = schema.decode(0x80, &field).unwrap().unwrap();
+
+        assert_eq!(value.get("counter"), Some(&Value::UInt(0x0102)));
+        assert_eq!(
+            value.get("vector"),
+            Some(&Value::Array(vec![Value::Float(1.5), Value::Float(-2.0)]))
+        );
+        assert_eq!(value.get("flag"), Some(&Value::Bool(true)));
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"counter":258,"vector":[1.5,-2.0],"flag":true}"#
+        );
+
+        assert!(schema
+            .decode(0x80, &RawField::new(0x60, vec![0x01]).unwrap())
+            .unwrap()
+            .is_err());
+        assert!(schema.decode(0x81, &field).is_none());
+    }
+
+    #[test]
+    fn builtin_schema() {
+        let bytes = vec![
+            0x75, 0x65, 0x80, 0x5E, 0x0E, 0x12, 0x40, 0x67, 0xD2, 0x7E, 0xF9, 0xDB, 0x22, 0xD1,
+            0x00, 0x00, 0x00, 0x06, 0x12, 0x0A, 0x3C, 0xB5, 0x86, 0xAA, 0x3D, 0xBE, 0xB0, 0x7E,
+            0x3F, 0x7E, 0xD0, 0x90, 0x3C, 0x10, 0xE8, 0xAB, 0x0E, 0x0C, 0x40, 0x47, 0xAB, 0x6C,
+            0x3D, 0x2D, 0xFD, 0xDC, 0x40, 0x3D, 0x17, 0xF4, 0x0E, 0x04, 0x3D, 0x36, 0xFC, 0xEA,
+            0xBC, 0xBE, 0x8D, 0xC0, 0x3F, 0x7F, 0x96, 0xDC, 0x0E, 0x05, 0x3A, 0x0A, 0x45, 0x73,
+            0x3A, 0xFB, 0x74, 0x4F, 0x3A, 0x6E, 0x7B, 0x95, 0x0E, 0x06, 0xBE, 0xD5, 0x4B, 0x19,
+            0x3D, 0x9D, 0x18, 0xC7, 0xBB, 0xE2, 0xCB, 0xE8, 0x06, 0x17, 0x44, 0x53, 0x1B, 0xB8,
+            0x3D, 0x55,
+        ];
+        let raw = RawPacket::from_bytes(&bytes).unwrap();
+
+        let mut schema = Schema::builtin();
+        schema.extend(Schema::from_toml(SCHEMA).unwrap());
+
+        let value = schema.decode_packet(&raw).unwrap();
+        let accel = value.get("ScaledAccelerometerVector").unwrap();
+        let correlation = value.get("GpsCorrelationTimestamp").unwrap();
+
+        let decoded = match data::Packet::new(&raw).unwrap() {
+            data::Packet::IMU(packet) => packet,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            accel.get("x"),
+            Some(&Value::Float(decoded.accelerometer.unwrap().x.into()))
+        );
+        assert_eq!(correlation.get("flags"), Some(&Value::UInt(6)));
+        assert!(schema.field(0x80, 0x60).is_some());
+    }
+}
@@ -0,0 +1,801 @@
+use data::{filter_data, gnss_data, imu_data, shared_data, Packet};
+use packet::Field;
+use packet::ReceiveTime;
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::time::{Duration, Instant, SystemTime};
+
+pub const SECONDS_PER_WEEK: f64 = 604_800.0;
+
+/// Seconds between the UNIX epoch and the GPS epoch (1980-01-06).
+pub const GPS_EPOCH_UNIX: f64 = 315_964_800.0;
+
+/// GPS week and time of week reported by the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsTimestamp {
+    pub week: u16,
+    pub tow: f64,
+    /// Whether the device clock was locked to a PPS signal, from the
+    /// correlation timestamp's `PPS_VALID` flag.
+    pub pps_locked: bool,
+}
+
+impl GpsTimestamp {
+    /// Seconds since the GPS epoch.
+    pub fn seconds(&self) -> f64 {
+        self.week as f64 * SECONDS_PER_WEEK + self.tow
+    }
+
+    /// Seconds from `earlier` to this time. Unlike subtracting `seconds`
+    /// this keeps sub-microsecond differences.
+    pub fn seconds_since(&self, earlier: &Self) -> f64 {
+        (self.week as i64 - earlier.week as i64) as f64 * SECONDS_PER_WEEK
+            + (self.tow - earlier.tow)
+    }
+
+    /// Nanoseconds since the GPS epoch.
+    pub fn nanos(&self) -> i128 {
+        self.week as i128 * SECONDS_PER_WEEK as i128 * 1_000_000_000
+            + (self.tow * 1e9).round() as i128
+    }
+
+    /// Extracts the device GPS time carried by a packet. The shared GPS
+    /// timestamp is preferred, times the device has not marked valid are
+    /// ignored.
+    pub fn from_packet(packet: &Packet) -> Option<Self> {
+        let shared = packet
+            .shared()
+            .and_then(|shared| shared.gps_timestamp)
+            .filter(|time| time.is_valid())
+            .map(|time| Self::from(&time));
+
+        if shared.is_some() {
+            return shared;
+        }
+
+        match packet {
+            Packet::IMU(packet) => packet
+                .gps_correlation
+                .filter(|time| time.is_valid())
+                .map(|time| Self::from(&time)),
+            Packet::GNSS(packet) => packet
+                .gps_time
+                .filter(|time| time.flags.tow_valid() && time.flags.week_valid())
+                .map(|time| Self::from(&time)),
+            Packet::FILTER(packet) => packet
+                .gps_time
+                .filter(|time| time.is_valid())
+                .map(|time| Self::from(&time)),
+            _ => None,
+        }
+    }
+
+    /// Resolves a week number that rolled over at 1024 weeks (as broadcast
+    /// by legacy receivers) to the full week closest to `reference`. Weeks
+    /// that are already past the first rollover are kept as they are.
+    pub fn with_week_near(self, reference: SystemTime) -> Self {
+        if self.week >= 1024 {
+            return self;
+        }
+
+        let reference = unix_seconds(reference) - GPS_EPOCH_UNIX;
+        let reference_week = (reference / SECONDS_PER_WEEK).floor() as i64;
+        let rollovers = ((reference_week - self.week as i64) as f64 / 1024.0).round() as i64;
+        let week = self.week as i64 + 1024 * rollovers.max(0);
+
+        Self {
+            week: week.min(u16::MAX as i64) as u16,
+            ..self
+        }
+    }
+
+    /// Seconds since the UNIX epoch in UTC. `leap_seconds` is the GPS to UTC
+    /// offset reported by the device, `LEAP_SECONDS` is used when it is not
+    /// known. Times within an inserted leap second map onto the following
+    /// second.
+    pub fn utc_seconds(&self, leap_seconds: Option<u8>) -> f64 {
+        let seconds = self.seconds();
+        let leap_seconds = leap_seconds.unwrap_or_else(|| leap_seconds_at(seconds));
+
+        GPS_EPOCH_UNIX + seconds - leap_seconds as f64
+    }
+
+    /// Nanoseconds since the UNIX epoch in UTC, the exact counterpart of
+    /// `utc_seconds`.
+    pub fn utc_nanos(&self, leap_seconds: Option<u8>) -> i128 {
+        let leap_seconds = leap_seconds.unwrap_or_else(|| leap_seconds_at(self.seconds()));
+
+        (GPS_EPOCH_UNIX as i128 - leap_seconds as i128) * 1_000_000_000 + self.nanos()
+    }
+
+    pub fn to_system_time(&self, leap_seconds: Option<u8>) -> Option<SystemTime> {
+        from_unix_nanos(self.utc_nanos(leap_seconds))
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(&self, leap_seconds: Option<u8>) -> Option<chrono::DateTime<chrono::Utc>> {
+        let nanos = self.utc_nanos(leap_seconds);
+        let seconds = nanos.div_euclid(1_000_000_000).try_into().ok()?;
+
+        chrono::DateTime::from_timestamp(seconds, nanos.rem_euclid(1_000_000_000) as u32)
+    }
+
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(&self, leap_seconds: Option<u8>) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(self.utc_nanos(leap_seconds)).ok()
+    }
+}
+
+macro_rules! gps_timestamp_from {
+    ($($field:ty),* $(,)?) => {
+        $(
+            impl From<&$field> for GpsTimestamp {
+                fn from(time: &$field) -> Self {
+                    Self {
+                        week: time.week,
+                        tow: time.tow,
+                        pps_locked: false,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+gps_timestamp_from!(
+    shared_data::GpsTimestamp,
+    gnss_data::GpsTime,
+    filter_data::GpsTime,
+);
+
+impl From<&imu_data::GpsCorrelationTimestamp> for GpsTimestamp {
+    fn from(time: &imu_data::GpsCorrelationTimestamp) -> Self {
+        Self {
+            week: time.week,
+            tow: time.tow,
+            pps_locked: time.flags.pps_valid(),
+        }
+    }
+}
+
+/// GPS to UTC offset in effect from each UTC time (seconds since the UNIX
+/// epoch) on, as published in IERS Bulletin C.
+pub const LEAP_SECONDS: &[(i64, u8)] = &[
+    (362_793_600, 1),
+    (394_329_600, 2),
+    (425_865_600, 3),
+    (489_024_000, 4),
+    (567_993_600, 5),
+    (631_152_000, 6),
+    (662_688_000, 7),
+    (709_948_800, 8),
+    (741_484_800, 9),
+    (773_020_800, 10),
+    (820_454_400, 11),
+    (867_715_200, 12),
+    (915_148_800, 13),
+    (1_136_073_600, 14),
+    (1_230_768_000, 15),
+    (1_341_100_800, 16),
+    (1_435_708_800, 17),
+    (1_483_228_800, 18),
+];
+
+/// GPS to UTC offset from `LEAP_SECONDS` at a GPS time in seconds since the
+/// GPS epoch.
+pub fn leap_seconds_at(gps_seconds: f64) -> u8 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(utc, leap)| gps_seconds >= (*utc as f64 - GPS_EPOCH_UNIX) + *leap as f64)
+        .map_or(0, |(_, leap)| *leap)
+}
+
+/// GPS to UTC offset reported by the device in a packet, if any.
+pub fn leap_seconds_from_packet(packet: &Packet) -> Option<u8> {
+    match packet {
+        Packet::GNSS(packet) => packet
+            .leap_seconds
+            .filter(|leap| leap.is_valid())
+            .map(|leap| leap.leap_seconds),
+        _ => None,
+    }
+}
+
+/// UTC time of a broken-down `UtcTime` field, `None` when the device has not
+/// marked it valid or the date or time of day does not exist. A second of 60
+/// (a leap second) maps onto the first second of the next minute.
+pub fn utc_time_to_system_time(time: &gnss_data::UtcTime) -> Option<SystemTime> {
+    let exists = (1..=12).contains(&time.month)
+        && (1..=days_in_month(time.year as i64, time.month as i64)).contains(&(time.day as i64))
+        && time.hour < 24
+        && time.minute < 60
+        && time.second <= 60
+        && time.millisecond < 1000;
+
+    if !time.is_valid() || !exists {
+        return None;
+    }
+
+    let days = days_from_civil(time.year as i64, time.month as i64, time.day as i64);
+    let seconds =
+        days * 86_400 + time.hour as i64 * 3_600 + time.minute as i64 * 60 + time.second as i64;
+
+    from_unix_nanos(seconds as i128 * 1_000_000_000 + time.millisecond as i128 * 1_000_000)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days between the UNIX epoch and a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn unix_nanos(time: SystemTime) -> i128 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(since) => since.as_nanos() as i128,
+        Err(before) => -(before.duration().as_nanos() as i128),
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> f64 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(since) => since.as_secs_f64(),
+        Err(before) => -before.duration().as_secs_f64(),
+    }
+}
+
+fn from_unix_nanos(nanos: i128) -> Option<SystemTime> {
+    let since = nanos.unsigned_abs();
+    let duration = Duration::new(
+        (since / 1_000_000_000).try_into().ok()?,
+        (since % 1_000_000_000) as u32,
+    );
+
+    if nanos >= 0 {
+        SystemTime::UNIX_EPOCH.checked_add(duration)
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(duration)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    device: f64,
+    host: f64,
+    weight: f64,
+}
+
+/// Linear fit of `host = intercept + slope * device`, both in seconds
+/// relative to the first sample.
+#[derive(Debug, Clone, Copy)]
+struct Fit {
+    intercept: f64,
+    slope: f64,
+    inliers: usize,
+}
+
+/// Estimates the offset and drift of the host clock against device GPS time
+/// from (receive time, device time) pairs.
+///
+/// Host time is measured on the monotonic clock and mapped to wall-clock time
+/// through the first sample, so adjustments to the system clock while running
+/// do not disturb the estimate. Both times are fitted relative to the first
+/// pair. Pairs whose latency is far from the rest of the window (by median
+/// absolute deviation) are left out of the fit. Pairs taken while the device
+/// was locked to PPS (`GpsTimestamp::pps_locked`) weigh more in the fit, as
+/// their device time is the more accurate.
+pub struct TimeSync {
+    capacity: usize,
+    threshold: f64,
+    pps_weight: f64,
+    base: Option<(ReceiveTime, GpsTimestamp)>,
+    samples: VecDeque<Sample>,
+    fit: Option<Fit>,
+}
+
+impl TimeSync {
+    /// Creates an estimator fitting over the latest `capacity` pairs.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            threshold: 3.0,
+            pps_weight: 10.0,
+            base: None,
+            samples: VecDeque::new(),
+            fit: None,
+        }
+    }
+
+    /// Number of scaled median absolute deviations a pair may be away from
+    /// the fit before it is rejected, 3 by default.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Weight of PPS locked pairs in the fit relative to the others, 10 by
+    /// default.
+    pub fn with_pps_weight(mut self, weight: f64) -> Self {
+        self.pps_weight = weight;
+        self
+    }
+
+    pub fn add(&mut self, received: ReceiveTime, device: GpsTimestamp) {
+        let (base, base_device) = *self.base.get_or_insert((received, device));
+
+        self.samples.push_back(Sample {
+            device: device.seconds_since(&base_device),
+            host: received
+                .monotonic
+                .saturating_duration_since(base.monotonic)
+                .as_secs_f64(),
+            weight: if device.pps_locked {
+                self.pps_weight
+            } else {
+                1.0
+            },
+        });
+
+        if self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+
+        self.fit = self.refit();
+    }
+
+    /// Adds the device time of a packet, if it carries a valid one.
+    pub fn add_packet(&mut self, received: ReceiveTime, packet: &Packet) {
+        if let Some(device) = GpsTimestamp::from_packet(packet) {
+            self.add(received, device);
+        }
+    }
+
+    /// Host seconds gained per device second, positive when the host clock
+    /// runs fast.
+    pub fn drift(&self) -> Option<f64> {
+        self.fit.map(|fit| fit.slope - 1.0)
+    }
+
+    /// Host UNIX time minus device GPS time (counted from the GPS epoch) at
+    /// the latest pair. This includes the GPS to UTC leap seconds.
+    pub fn offset(&self) -> Option<f64> {
+        let device = self.samples.back()?.device;
+        let (base, base_device) = self.base?;
+        let host = self.to_host_seconds(device)?;
+        let base_offset =
+            unix_nanos(base.system) - GPS_EPOCH_UNIX as i128 * 1_000_000_000 - base_device.nanos();
+
+        Some(base_offset as f64 / 1e9 + (host - device))
+    }
+
+    /// Number of pairs used by the current fit.
+    pub fn inliers(&self) -> usize {
+        self.fit.map_or(0, |fit| fit.inliers)
+    }
+
+    /// Monotonic host time corresponding to a device time.
+    pub fn to_instant(&self, device: GpsTimestamp) -> Option<Instant> {
+        let (base, base_device) = self.base?;
+        let host = self.to_host_seconds(device.seconds_since(&base_device))?;
+
+        shift(base.monotonic, host)
+    }
+
+    /// Wall-clock host time corresponding to a device time.
+    pub fn to_system_time(&self, device: GpsTimestamp) -> Option<SystemTime> {
+        let (base, base_device) = self.base?;
+        let host = self.to_host_seconds(device.seconds_since(&base_device))?;
+
+        if host >= 0.0 {
+            base.system.checked_add(Duration::from_secs_f64(host))
+        } else {
+            base.system.checked_sub(Duration::from_secs_f64(-host))
+        }
+    }
+
+    /// Host UNIX time in seconds corresponding to a device time.
+    pub fn to_unix(&self, device: GpsTimestamp) -> Option<f64> {
+        self.to_system_time(device)?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .map(|time| time.as_secs_f64())
+    }
+
+    fn to_host_seconds(&self, device: f64) -> Option<f64> {
+        self.fit.map(|fit| fit.intercept + fit.slope * device)
+    }
+
+    fn refit(&self) -> Option<Fit> {
+        let samples: Vec<Sample> = self.samples.iter().copied().collect();
+        let fit = least_squares(&samples)?;
+
+        let mut residuals: Vec<f64> = samples.iter().map(|s| residual(&fit, s)).collect();
+        let center = median(&mut residuals);
+        let mut deviations: Vec<f64> = residuals.iter().map(|r| (r - center).abs()).collect();
+        let limit = (self.threshold * 1.4826 * median(&mut deviations)).max(1e-9);
+
+        let inliers: Vec<Sample> = samples
+            .iter()
+            .copied()
+            .filter(|s| (residual(&fit, s) - center).abs() <= limit)
+            .collect();
+
+        least_squares(&inliers).or(Some(fit))
+    }
+}
+
+fn shift(instant: Instant, seconds: f64) -> Option<Instant> {
+    if seconds >= 0.0 {
+        instant.checked_add(Duration::from_secs_f64(seconds))
+    } else {
+        instant.checked_sub(Duration::from_secs_f64(-seconds))
+    }
+}
+
+fn residual(fit: &Fit, sample: &Sample) -> f64 {
+    sample.host - (fit.intercept + fit.slope * sample.device)
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    match values.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => values[n / 2],
+        n => (values[n / 2 - 1] + values[n / 2]) / 2.0,
+    }
+}
+
+fn least_squares(samples: &[Sample]) -> Option<Fit> {
+    match samples {
+        [] => None,
+        [sample] => Some(Fit {
+            intercept: sample.host - sample.device,
+            slope: 1.0,
+            inliers: 1,
+        }),
+        _ => {
+            let total = samples.iter().map(|s| s.weight).sum::<f64>();
+            let mean_device = samples.iter().map(|s| s.weight * s.device).sum::<f64>() / total;
+            let mean_host = samples.iter().map(|s| s.weight * s.host).sum::<f64>() / total;
+
+            let mut covariance = 0.0;
+            let mut variance = 0.0;
+            for sample in samples {
+                covariance +=
+                    sample.weight * (sample.device - mean_device) * (sample.host - mean_host);
+                variance += sample.weight * (sample.device - mean_device).powi(2);
+            }
+
+            // All pairs at the same device time, only the offset is known.
+            let slope = if variance > 0.0 {
+                covariance / variance
+            } else {
+                1.0
+            };
+
+            Some(Fit {
+                intercept: mean_host - slope * mean_device,
+                slope,
+                inliers: samples.len(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(base: ReceiveTime, seconds: f64) -> ReceiveTime {
+        ReceiveTime {
+            monotonic: base.monotonic + Duration::from_secs_f64(seconds),
+            system: base.system + Duration::from_secs_f64(seconds),
+        }
+    }
+
+    #[test]
+    fn drift_and_outliers() {
+        let base = ReceiveTime::now();
+        let mut sync = TimeSync::new(100);
+
+        for i in 0..100 {
+            let device = 10.0 * i as f64;
+            // 50 ppm fast host clock with a few milliseconds of jittery
+            // latency and an occasional badly delayed read.
+            let latency = 0.002 + 0.001 * ((i * 7) % 5) as f64;
+            let delay = if i % 17 == 3 { 0.5 } else { 0.0 };
+            let host = device * (1.0 + 50e-6) + latency + delay;
+
+            sync.add(
+                received(base, host),
+                GpsTimestamp {
+                    week: 2200,
+                    tow: 1000.0 + device,
+                    pps_locked: false,
+                },
+            );
+        }
+
+        assert!((sync.drift().unwrap() - 50e-6).abs() < 1e-6);
+        assert!(sync.inliers() < 100);
+
+        let device = GpsTimestamp {
+            week: 2200,
+            tow: 1500.0,
+            pps_locked: false,
+        };
+        let expected = base.monotonic + Duration::from_secs_f64(500.0 * (1.0 + 50e-6) + 0.004);
+        let instant = sync.to_instant(device).unwrap();
+        let error = if instant > expected {
+            instant - expected
+        } else {
+            expected - instant
+        };
+
+        assert!(error < Duration::from_millis(2));
+    }
+
+    #[test]
+    fn single_sample() {
+        let base = ReceiveTime::now();
+        let mut sync = TimeSync::new(10);
+        let device = GpsTimestamp {
+            week: 2200,
+            tow: 10.0,
+            pps_locked: false,
+        };
+
+        sync.add(base, device);
+
+        assert_eq!(sync.drift(), Some(0.0));
+        assert_eq!(sync.to_instant(device), Some(base.monotonic));
+    }
+
+    #[test]
+    fn pps_locked_pairs_weighted() {
+        let base = ReceiveTime::now();
+        let mut weighted = TimeSync::new(100);
+        let mut unweighted = TimeSync::new(100).with_pps_weight(1.0);
+
+        for i in 0..40 {
+            // Without PPS lock the device time wanders by a few milliseconds,
+            // every read arrives 2 ms after it was sent.
+            let pps_locked = i % 4 == 0;
+            let error = if pps_locked {
+                0.0
+            } else {
+                [0.004, -0.002, 0.003, -0.001, 0.005, 0.002][i % 6]
+            };
+            let device = GpsTimestamp {
+                week: 2200,
+                tow: 100.0 + i as f64 + error,
+                pps_locked,
+            };
+
+            weighted.add(received(base, i as f64 + 0.002), device);
+            unweighted.add(received(base, i as f64 + 0.002), device);
+        }
+
+        assert_eq!(weighted.inliers(), 40);
+
+        let error = |sync: &TimeSync| {
+            let instant = sync
+                .to_instant(GpsTimestamp {
+                    week: 2200,
+                    tow: 110.0,
+                    pps_locked: false,
+                })
+                .unwrap();
+            let expected = base.monotonic + Duration::from_secs_f64(10.002);
+
+            if instant > expected {
+                instant - expected
+            } else {
+                expected - instant
+            }
+        };
+
+        assert!(error(&weighted) < Duration::from_micros(300));
+        assert!(error(&unweighted) > Duration::from_micros(500));
+    }
+
+    #[test]
+    fn sub_microsecond_differences() {
+        let earlier = GpsTimestamp {
+            week: 2200,
+            tow: 1000.0,
+            pps_locked: true,
+        };
+        let later = GpsTimestamp {
+            tow: 1_000.000_000_1,
+            ..earlier
+        };
+
+        assert!((later.seconds_since(&earlier) - 1e-7).abs() < 1e-12);
+        assert_eq!(later.nanos() - earlier.nanos(), 100);
+        assert_eq!(
+            GpsTimestamp {
+                week: 2201,
+                tow: 0.0,
+                ..earlier
+            }
+            .seconds_since(&earlier),
+            SECONDS_PER_WEEK - 1000.0
+        );
+    }
+
+    #[test]
+    fn utc_conversion() {
+        // GPS week 2200 started on 2022-03-06, 18 leap seconds after UTC.
+        let time = GpsTimestamp {
+            week: 2200,
+            tow: 18.5,
+            pps_locked: false,
+        };
+
+        assert_eq!(leap_seconds_at(time.seconds()), 18);
+        assert_eq!(leap_seconds_at(0.0), 0);
+        assert_eq!(time.utc_seconds(None), 1_646_524_800.5);
+        assert_eq!(time.utc_seconds(Some(17)), 1_646_524_801.5);
+        assert_eq!(
+            time.to_system_time(None),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_646_524_800_500))
+        );
+
+        // The offset steps to 17 once GPS time reaches UTC 2015-07-01.
+        let step = 1_435_708_800.0 - GPS_EPOCH_UNIX + 17.0;
+        assert_eq!(leap_seconds_at(step - 0.5), 16);
+        assert_eq!(leap_seconds_at(step), 17);
+    }
+
+    #[test]
+    fn nanosecond_conversion() {
+        let time = GpsTimestamp {
+            week: 2200,
+            tow: 18.123_456_789,
+            pps_locked: false,
+        };
+
+        assert_eq!(time.utc_nanos(None), 1_646_524_800_123_456_789);
+        assert_eq!(
+            time.to_system_time(None),
+            Some(SystemTime::UNIX_EPOCH + Duration::new(1_646_524_800, 123_456_789))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversion() {
+        let time = GpsTimestamp {
+            week: 2200,
+            tow: 18.123_456_789,
+            pps_locked: false,
+        };
+        let date = time.to_chrono(None).unwrap();
+
+        assert_eq!(date.to_rfc3339(), "2022-03-06T00:00:00.123456789+00:00");
+        assert_eq!(time.to_chrono(Some(17)).unwrap().timestamp(), 1_646_524_801);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_conversion() {
+        let timestamp = GpsTimestamp {
+            week: 2200,
+            tow: 18.123_456_789,
+            pps_locked: false,
+        };
+        let date = timestamp.to_offset_date_time(None).unwrap();
+
+        assert_eq!(date.unix_timestamp(), 1_646_524_800);
+        assert_eq!(date.nanosecond(), 123_456_789);
+        assert_eq!(
+            (date.year(), date.month(), date.day()),
+            (2022, time::Month::March, 6)
+        );
+    }
+
+    #[test]
+    fn week_rollover() {
+        let reference = SystemTime::UNIX_EPOCH + Duration::from_secs(1_646_524_800);
+        let rolled = GpsTimestamp {
+            week: 2200 - 2048,
+            tow: 10.0,
+            pps_locked: false,
+        };
+
+        assert_eq!(rolled.with_week_near(reference).week, 2200);
+        assert_eq!(
+            GpsTimestamp {
+                week: 2200,
+                ..rolled
+            }
+            .with_week_near(reference)
+            .week,
+            2200
+        );
+    }
+
+    #[test]
+    fn utc_time_field() {
+        let time = gnss_data::UtcTime {
+            year: 2022,
+            month: 3,
+            day: 6,
+            hour: 0,
+            minute: 0,
+            second: 1,
+            millisecond: 250,
+            flags: gnss_data::UtcTimeFlags::DATE_TIME_VALID,
+        };
+
+        assert_eq!(
+            utc_time_to_system_time(&time),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_646_524_801_250))
+        );
+        assert_eq!(
+            utc_time_to_system_time(&gnss_data::UtcTime {
+                flags: gnss_data::UtcTimeFlags::empty(),
+                ..time
+            }),
+            None
+        );
+    }
+    #[test]
+    fn utc_time_field_rejects_missing_dates() {
+        let time = gnss_data::UtcTime {
+            year: 2024,
+            month: 2,
+            day: 29,
+            hour: 23,
+            minute: 59,
+            second: 59,
+            millisecond: 999,
+            flags: gnss_data::UtcTimeFlags::DATE_TIME_VALID,
+        };
+
+        assert!(utc_time_to_system_time(&time).is_some());
+        assert!(utc_time_to_system_time(&gnss_data::UtcTime { second: 60, ..time }).is_some());
+
+        let missing = [
+            gnss_data::UtcTime { day: 30, ..time },
+            gnss_data::UtcTime { year: 2023, ..time },
+            gnss_data::UtcTime { year: 1900, ..time },
+            gnss_data::UtcTime {
+                month: 4,
+                day: 31,
+                ..time
+            },
+            gnss_data::UtcTime { month: 13, ..time },
+            gnss_data::UtcTime { day: 0, ..time },
+            gnss_data::UtcTime { hour: 24, ..time },
+            gnss_data::UtcTime { minute: 60, ..time },
+            gnss_data::UtcTime { second: 61, ..time },
+            gnss_data::UtcTime {
+                millisecond: 1000,
+                ..time
+            },
+        ];
+
+        for time in missing {
+            assert_eq!(utc_time_to_system_time(&time), None, "{:?}", time);
+        }
+    }
+}
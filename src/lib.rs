@@ -1,4 +1,8 @@
+pub use capture;
+pub use convert;
 pub use data;
 pub use error;
 pub use packet;
 pub use parser;
+pub use schema;
+pub use timing;
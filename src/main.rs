@@ -0,0 +1,462 @@
+use anyhow::{anyhow, bail, Context, Result};
+use capture::{Pacing, Recorder, Replay};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use convert::Format;
+use data::Packet;
+use packet::{unframe, PacketComponent, RawField, RawPacket, ReceiveTime};
+use parser::LordParser;
+use schema::Schema;
+use serialport::{SerialPort, SerialPortType};
+use std::convert::TryInto;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// USB vendor id of LORD MicroStrain devices.
+const LORD_VID: u16 = 0x199B;
+
+const BASE_COMMAND: u8 = 0x01;
+const DM_COMMAND: u8 = 0x0C;
+const ACK_NACK: u8 = 0xF1;
+
+#[derive(Parser)]
+#[command(
+    name = "lordserial",
+    version,
+    about = "Talk to LORD MicroStrain 3DM/GX5 devices"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List serial ports of USB devices with the LORD vendor ID
+    List {
+        /// Also list ports of other devices
+        #[arg(long)]
+        all: bool,
+    },
+    /// Print the device information
+    Info(Connection),
+    /// Decode packets and print them as JSON lines
+    Stream {
+        #[command(flatten)]
+        connection: Connection,
+        #[command(flatten)]
+        decoding: Decoding,
+    },
+    /// Record every byte read from the device into a capture file
+    Record {
+        #[command(flatten)]
+        connection: Connection,
+        path: PathBuf,
+        /// Start a new file once the current one holds this many bytes
+        #[arg(long)]
+        max_bytes: Option<u64>,
+        /// Start a new file once the current one spans this many seconds
+        #[arg(long)]
+        max_seconds: Option<u64>,
+    },
+    /// Decode a capture file and print its packets as JSON lines
+    Replay {
+        path: PathBuf,
+        #[arg(long, value_enum, default_value = "fast")]
+        pacing: ReplayPacing,
+        /// Playback speed relative to the recording
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+        /// Start from this many seconds into the recording
+        #[arg(long)]
+        seek: Option<f64>,
+        #[command(flatten)]
+        decoding: Decoding,
+    },
+    /// List every field the decoder knows with its layout
+    Fields,
+    /// Convert a capture into one table per field
+    Convert {
+        input: PathBuf,
+        /// Directory the tables are written to
+        output: PathBuf,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: TableFormat,
+    },
+    /// Read, change or save device settings
+    Config {
+        #[command(flatten)]
+        connection: Connection,
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Args)]
+struct Connection {
+    /// Serial port of the device
+    #[arg(long)]
+    port: String,
+    #[arg(long, default_value_t = 115_200)]
+    baud: u32,
+}
+
+impl Connection {
+    fn open(&self) -> Result<Box<dyn SerialPort>> {
+        serialport::new(&self.port, self.baud)
+            .timeout(Duration::from_millis(100))
+            .open()
+            .with_context(|| format!("opening {}", self.port))
+    }
+}
+
+#[derive(Args)]
+struct Decoding {
+    /// Decode with the built in field layouts extended by a TOML or JSON
+    /// schema file, instead of the typed decoder
+    #[arg(long)]
+    schema: Option<PathBuf>,
+}
+
+impl Decoding {
+    fn schema(&self) -> Result<Option<Schema>> {
+        self.schema
+            .as_ref()
+            .map(|path| {
+                let mut schema = Schema::builtin();
+                schema.extend(Schema::load(path)?);
+                Ok(schema)
+            })
+            .transpose()
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReplayPacing {
+    Fast,
+    Host,
+    Gps,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TableFormat {
+    Csv,
+    Jsonl,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the current value of a setting
+    Get { setting: Setting },
+    /// Change a setting until the device is power cycled
+    Set { setting: Setting, value: String },
+    /// Save the current settings as startup settings
+    Save,
+}
+
+/// Settings that take a function selector, `value` is a baud rate for
+/// `baudrate` and `descriptor:decimation,...` for the message formats.
+#[derive(Clone, Copy, ValueEnum)]
+enum Setting {
+    Baudrate,
+    ImuFormat,
+    GnssFormat,
+    FilterFormat,
+}
+
+impl Setting {
+    fn descriptor(self) -> u8 {
+        match self {
+            Self::Baudrate => 0x40,
+            Self::ImuFormat => 0x08,
+            Self::GnssFormat => 0x09,
+            Self::FilterFormat => 0x0A,
+        }
+    }
+
+    fn encode(self, value: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Baudrate => Ok(value.parse::<u32>()?.to_be_bytes().to_vec()),
+            _ => {
+                let mut bytes = vec![0u8];
+
+                for entry in value.split(',').filter(|entry| !entry.is_empty()) {
+                    let (descriptor, decimation) = entry
+                        .split_once(':')
+                        .ok_or_else(|| anyhow!("expected descriptor:decimation, got {}", entry))?;
+
+                    bytes.push(parse_u8(descriptor)?);
+                    bytes.extend(decimation.parse::<u16>()?.to_be_bytes());
+                    bytes[0] += 1;
+                }
+
+                Ok(bytes)
+            }
+        }
+    }
+
+    fn decode(self, data: &[u8]) -> Result<String> {
+        match self {
+            Self::Baudrate => Ok(u32::from_be_bytes(data.try_into()?).to_string()),
+            _ => Ok(data
+                .get(1..)
+                .unwrap_or_default()
+                .chunks_exact(3)
+                .map(|entry| {
+                    let decimation = u16::from_be_bytes([entry[1], entry[2]]);
+                    format!("{:#04x}:{}", entry[0], decimation)
+                })
+                .collect::<Vec<_>>()
+                .join(",")),
+        }
+    }
+}
+
+fn parse_u8(value: &str) -> Result<u8> {
+    Ok(match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16)?,
+        None => value.parse()?,
+    })
+}
+
+/// Sends a command and waits for its ACK, returning the reply fields after
+/// the ACK.
+fn command(
+    port: &mut dyn SerialPort,
+    set: u8,
+    descriptor: u8,
+    data: Vec<u8>,
+) -> Result<Vec<RawField>> {
+    let request = RawPacket::new(set, vec![RawField::new(descriptor, data)?])?;
+    port.write_all(&request.to_bytes()?)?;
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    let mut bytes = Vec::new();
+    let mut chunk = [0u8; 256];
+
+    while Instant::now() < deadline {
+        match port.read(&mut chunk) {
+            Ok(count) => bytes.extend_from_slice(&chunk[..count]),
+            Err(ref e) if e.kind() == ErrorKind::TimedOut => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        for reply in unframe(&mut bytes) {
+            if reply.header.descriptor != set {
+                continue;
+            }
+
+            let mut fields = reply.payload.fields.into_iter();
+            let ack = fields.by_ref().find(|field| {
+                field.descriptor == ACK_NACK && field.data.first() == Some(&descriptor)
+            });
+
+            match ack.map(|ack| ack.data.get(1).copied()) {
+                Some(Some(0)) => return Ok(fields.collect()),
+                Some(code) => bail!(
+                    "device rejected command {:#04x} {:#04x} with error {:?}",
+                    set,
+                    descriptor,
+                    code
+                ),
+                None => (),
+            }
+        }
+    }
+
+    bail!("no reply to command {:#04x} {:#04x}", set, descriptor)
+}
+
+/// Handler printing every packet as a JSON line. Packets that cannot be
+/// decoded, such as those from unknown descriptor sets, get an error line
+/// instead.
+fn packet_printer(schema: Option<Schema>) -> impl Fn(RawPacket, ReceiveTime) {
+    move |packet, _| {
+        let line = match &schema {
+            Some(schema) => schema
+                .decode_packet(&packet)
+                .and_then(|value| Ok(serde_json::to_string(&value)?)),
+            None => Packet::new(&packet).and_then(|packet| Ok(serde_json::to_string(&packet)?)),
+        };
+
+        match line {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!(
+                "skipping packet from set {:#04x}: {:#}",
+                packet.header.descriptor, e
+            ),
+        }
+    }
+}
+
+fn list(all: bool) -> Result<()> {
+    for port in serialport::available_ports()? {
+        let usb = match &port.port_type {
+            SerialPortType::UsbPort(usb) => Some(usb),
+            _ => None,
+        };
+
+        if all || usb.is_some_and(|usb| usb.vid == LORD_VID) {
+            match usb {
+                Some(usb) => println!(
+                    "{}\t{:04x}:{:04x}\t{}\t{}",
+                    port.port_name,
+                    usb.vid,
+                    usb.pid,
+                    usb.product.as_deref().unwrap_or(""),
+                    usb.serial_number.as_deref().unwrap_or("")
+                ),
+                None => println!("{}", port.port_name),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn fields() {
+    for description in data::registry() {
+        println!(
+            "{:#04x} {:#04x}\t{}\t{} bytes",
+            description.set, description.descriptor, description.name, description.size
+        );
+
+        for member in description.members {
+            println!("\t{:>3}\t{}\t{}", member.offset, member.name, member.ty);
+        }
+    }
+}
+
+fn info(connection: &Connection) -> Result<()> {
+    let mut port = connection.open()?;
+    let fields = command(port.as_mut(), BASE_COMMAND, 0x03, vec![])?;
+    let info = fields
+        .iter()
+        .find(|field| field.descriptor == 0x81 && field.data.len() >= 82)
+        .ok_or_else(|| anyhow!("device info missing from reply"))?;
+
+    let firmware = u16::from_be_bytes([info.data[0], info.data[1]]);
+    let text = |index: usize| {
+        let start = 2 + 16 * index;
+        String::from_utf8_lossy(&info.data[start..start + 16])
+            .trim()
+            .to_string()
+    };
+
+    println!(
+        "firmware\t{}.{}.{:02}",
+        firmware / 1000,
+        firmware / 100 % 10,
+        firmware % 100
+    );
+    println!("model\t{}", text(0));
+    println!("model number\t{}", text(1));
+    println!("serial number\t{}", text(2));
+    println!("lot number\t{}", text(3));
+    println!("options\t{}", text(4));
+
+    Ok(())
+}
+
+fn config(connection: &Connection, action: &ConfigAction) -> Result<()> {
+    let mut port = connection.open()?;
+
+    match action {
+        ConfigAction::Get { setting } => {
+            let fields = command(port.as_mut(), DM_COMMAND, setting.descriptor(), vec![0x02])?;
+            let reply = fields
+                .first()
+                .ok_or_else(|| anyhow!("setting missing from reply"))?;
+
+            println!("{}", setting.decode(&reply.data)?);
+        }
+        ConfigAction::Set { setting, value } => {
+            let mut data = vec![0x01];
+            data.extend(setting.encode(value)?);
+
+            command(port.as_mut(), DM_COMMAND, setting.descriptor(), data)?;
+        }
+        ConfigAction::Save => {
+            command(port.as_mut(), DM_COMMAND, 0x30, vec![0x03])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::List { all } => list(all)?,
+        Command::Info(connection) => info(&connection)?,
+        Command::Stream {
+            connection,
+            decoding,
+        } => LordParser::new(connection.open()?, packet_printer(decoding.schema()?)).parse()?,
+        Command::Record {
+            connection,
+            path,
+            max_bytes,
+            max_seconds,
+        } => {
+            let mut recorder = Recorder::new(path);
+
+            if let Some(bytes) = max_bytes {
+                recorder = recorder.with_max_bytes(bytes);
+            }
+
+            if let Some(seconds) = max_seconds {
+                recorder = recorder.with_max_duration(Duration::from_secs(seconds));
+            }
+
+            LordParser::new(connection.open()?, |_, _| ())
+                .with_recording(recorder.start()?)
+                .parse()?;
+        }
+        Command::Replay {
+            path,
+            pacing,
+            speed,
+            seek,
+            decoding,
+        } => {
+            let pacing = match pacing {
+                ReplayPacing::Fast => Pacing::Fast,
+                ReplayPacing::Host => Pacing::Host,
+                ReplayPacing::Gps => Pacing::Gps,
+            };
+            let mut replay = Replay::open(path)?.with_pacing(pacing).with_speed(speed);
+
+            if let Some(seconds) = seek {
+                replay.seek(Duration::from_secs_f64(seconds.max(0.0)))?;
+            }
+
+            LordParser::from_reader(Box::new(replay), packet_printer(decoding.schema()?))
+                .parse()?;
+        }
+        Command::Fields => fields(),
+        Command::Convert {
+            input,
+            output,
+            format,
+        } => {
+            let format = match format {
+                TableFormat::Csv => Format::Csv,
+                TableFormat::Jsonl => Format::Jsonl,
+                #[cfg(feature = "parquet")]
+                TableFormat::Parquet => Format::Parquet,
+            };
+
+            for (descriptor, count) in convert::convert(input, output, format)? {
+                eprintln!(
+                    "skipped {} packets from unknown descriptor set {:#04x}",
+                    count, descriptor
+                );
+            }
+        }
+        Command::Config { connection, action } => config(&connection, &action)?,
+    }
+
+    Ok(())
+}